@@ -36,9 +36,34 @@ use pyo3::types::{PyDict, PyModule};
 use pyo3::Bound;
 use numpy::ToPyArray;
 use shared::{Frame as RustFrame, FieldWhitelist, CoordinateData};
-use shared::coordinates::FieldType;
+use shared::coordinates::{FieldType, Roi};
 use std::collections::HashMap;
 
+/// Parse a `roi=` dict into a [`Roi`].
+///
+/// Recognises `x`/`y`/`z` → `(f64, f64)` bounds, `intensity` → `(u16, u16)`
+/// bounds, and `over_range` → `bool`. Unknown keys are ignored for forward
+/// compatibility.
+fn parse_roi(dict: &Bound<'_, PyDict>) -> PyResult<Roi> {
+    let mut roi = Roi::default();
+    if let Some(v) = dict.get_item("x")? {
+        roi.x = Some(v.extract()?);
+    }
+    if let Some(v) = dict.get_item("y")? {
+        roi.y = Some(v.extract()?);
+    }
+    if let Some(v) = dict.get_item("z")? {
+        roi.z = Some(v.extract()?);
+    }
+    if let Some(v) = dict.get_item("intensity")? {
+        roi.intensity = Some(v.extract()?);
+    }
+    if let Some(v) = dict.get_item("over_range")? {
+        roi.over_range = Some(v.extract()?);
+    }
+    Ok(roi)
+}
+
 /// Python wrapper for the Rust Frame struct
 #[pyclass(name = "Frame")]
 pub struct PyFrame {
@@ -91,26 +116,87 @@ impl PyFrame {
     ///     decimation: Take every Nth point (default: 1, no decimation)
     ///     field_whitelist: List of field names to extract (default: all fields)
     ///     time_unit: Time unit for compatibility (ignored, for pyfg compatibility)
-    /// 
+    ///     threads: Decode pixels in parallel across this many threads (default:
+    ///         None = serial; 0 = rayon's global pool). Requires the `parallel`
+    ///         feature.
+    ///     roi: Optional region-of-interest applied at decode time, e.g.
+    ///         ``{'x': (a, b), 'y': (c, d), 'z': (e, f), 'intensity': (lo, hi),
+    ///         'over_range': False}``. Points outside the named bounds are
+    ///         dropped before they are materialised.
+    ///
     /// Returns:
     ///     Numpy structured array with the requested fields
-    #[pyo3(signature = (decimation=1, field_whitelist=None, time_unit=None))]
+    #[pyo3(signature = (decimation=1, field_whitelist=None, time_unit=None, threads=None, roi=None))]
     fn data(
         &self,
         py: Python,
         decimation: Option<usize>,
         field_whitelist: Option<Vec<String>>,
         time_unit: Option<&str>, // Ignored, for pyfg compatibility
+        threads: Option<usize>,
+        roi: Option<&Bound<'_, PyDict>>,
     ) -> PyResult<PyObject> {
         let decimation = decimation.unwrap_or(1);
-        
+
         // Extract coordinate data
         let field_whitelist_strs: Option<Vec<&str>> = field_whitelist.as_ref().map(|v| v.iter().map(|s| s.as_str()).collect());
-        let coord_data = self.inner.data(Some(decimation), field_whitelist_strs.as_deref());
-        
+        let roi = roi.map(parse_roi).transpose()?;
+        let coord_data = match threads {
+            #[cfg(feature = "parallel")]
+            Some(threads) => self.inner.data_parallel(
+                Some(decimation),
+                field_whitelist_strs.as_deref(),
+                roi.as_ref(),
+                threads,
+            ),
+            _ => self.inner.data(Some(decimation), field_whitelist_strs.as_deref(), roi.as_ref()),
+        };
+
         // Convert to numpy structured array
         coordinate_data_to_numpy(py, &coord_data, field_whitelist_strs.as_deref())
     }
+
+    /// Extract coordinate data as an Apache Arrow `RecordBatch`.
+    ///
+    /// Unlike [`data`](Self::data), which rebuilds a NumPy structured array
+    /// field-by-field, this builds one typed Arrow column per requested field
+    /// and hands it to `pyarrow` over the Arrow C Data Interface, so
+    /// `pyarrow.RecordBatch` / Polars can ingest the columns without copying.
+    ///
+    /// Args:
+    ///     decimation: Take every Nth point (default: 1, no decimation)
+    ///     field_whitelist: List of field names to extract (default: all fields)
+    ///
+    /// Returns:
+    ///     A `pyarrow.RecordBatch` with x/y/z as float64, intensity as uint16,
+    ///     and gain/over_range as boolean columns.
+    #[cfg(feature = "arrow")]
+    #[pyo3(signature = (decimation=1, field_whitelist=None))]
+    fn data_arrow(
+        &self,
+        py: Python,
+        decimation: Option<usize>,
+        field_whitelist: Option<Vec<String>>,
+    ) -> PyResult<PyObject> {
+        use arrow::pyarrow::ToPyArrow;
+
+        let decimation = decimation.unwrap_or(1);
+
+        let field_whitelist_strs: Option<Vec<&str>> = field_whitelist
+            .as_ref()
+            .map(|v| v.iter().map(|s| s.as_str()).collect());
+        let coord_data = self.inner.data(Some(decimation), field_whitelist_strs.as_deref(), None);
+
+        let whitelist = match field_whitelist_strs.as_deref() {
+            Some(fields) => FieldWhitelist::new(fields),
+            None => FieldWhitelist::all(),
+        };
+
+        let batch = shared::coordinate_data_to_arrow(&coord_data, &whitelist)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to build Arrow batch: {}", e)))?;
+
+        batch.to_pyarrow(py)
+    }
 }
 
 /// Convert CoordinateData to a numpy structured array (recarray)
@@ -133,54 +219,52 @@ fn coordinate_data_to_numpy(
     
     let n_points = coord_data.len();
     let mut arrays = HashMap::new();
-    
-    // Prepare data arrays for each field
+
+    // The float columns are already contiguous `f64` buffers, so wrap them
+    // straight into NumPy arrays without rebuilding a per-field `Vec`.
     if whitelist.includes(&FieldType::X) {
-        let mut x_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            x_data.push(point.x.unwrap_or(0.0));
+        if let Some(x) = coord_data.x_values() {
+            arrays.insert("x", x.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("x", x_data.to_pyarray_bound(py).to_object(py));
     }
-    
+
     if whitelist.includes(&FieldType::Y) {
-        let mut y_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            y_data.push(point.y.unwrap_or(0.0));
+        if let Some(y) = coord_data.y_values() {
+            arrays.insert("y", y.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("y", y_data.to_pyarray_bound(py).to_object(py));
     }
 
     if whitelist.includes(&FieldType::Z) {
-        let mut z_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            z_data.push(point.z.unwrap_or(0.0));
+        if let Some(z) = coord_data.z_values() {
+            arrays.insert("z", z.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("z", z_data.to_pyarray_bound(py).to_object(py));
     }
 
+    // The structured array is all-`f8`, so the remaining columns are promoted to
+    // `f64` here — still a single linear pass over each contiguous column.
     if whitelist.includes(&FieldType::Intensity) {
-        let mut intensity_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            intensity_data.push(point.intensity.unwrap_or(0) as f64);
+        if let Some(intensity) = coord_data.intensity_values() {
+            let data: Vec<f64> = intensity.iter().map(|&v| v as f64).collect();
+            arrays.insert("intensity", data.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("intensity", intensity_data.to_pyarray_bound(py).to_object(py));
     }
 
     if whitelist.includes(&FieldType::Gain) {
-        let mut gain_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            gain_data.push(if point.gain.unwrap_or(false) { 1.0 } else { 0.0 });
+        if let Some(gain) = coord_data.gain.as_ref() {
+            let data: Vec<f64> = (0..n_points)
+                .map(|i| if gain.get(i).unwrap_or(false) { 1.0 } else { 0.0 })
+                .collect();
+            arrays.insert("gain", data.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("gain", gain_data.to_pyarray_bound(py).to_object(py));
     }
 
     if whitelist.includes(&FieldType::OverRange) {
-        let mut over_range_data = Vec::with_capacity(n_points);
-        for point in &coord_data.points {
-            over_range_data.push(if point.over_range.unwrap_or(false) { 1.0 } else { 0.0 });
+        if let Some(over_range) = coord_data.over_range.as_ref() {
+            let data: Vec<f64> = (0..n_points)
+                .map(|i| if over_range.get(i).unwrap_or(false) { 1.0 } else { 0.0 })
+                .collect();
+            arrays.insert("over_range", data.to_pyarray_bound(py).to_object(py));
         }
-        arrays.insert("over_range", over_range_data.to_pyarray_bound(py).to_object(py));
     }
     
     // Create a structured array using numpy
@@ -5,19 +5,76 @@ This module provides the core frame capture functionality, receiving UDP packets
 and writing them as .dsql files with proper frame boundary detection.
 */
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::SyncSender;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 use tokio::net::UdpSocket;
 use tokio::runtime::Runtime;
 use tracing::{info, warn, error};
-use crossbeam_channel::{bounded, Receiver, Sender};
 use shared::frame::Frame;
-use shared::coordinates::FieldWhitelist;
+use shared::coordinates::{CoordinateData, FieldWhitelist};
+use shared::transform::{Transform, TransformChain};
+use shared::hword::Endianness;
+use crate::codec::FrameCodec;
+use crate::compression::FrameCompressor;
+use crate::config::{CompressionConfig, PixelCountLayout};
+use crate::output_sink::OutputSinkConfig;
 use serde_json;
 use chrono::Local;
 use crate::frame_sync::FrameSyncEngine;
+use crate::ring_buffer::GulpRing;
+
+/// Time-based frame-rate limiter for the output stage.
+///
+/// Gates frame emission to a target FPS by dropping whole frames; the first
+/// frame after each interval elapses is kept, so a slow consumer always sees the
+/// most recent frame rather than a stale backlog. A `None` target emits every
+/// frame (the historical behavior).
+struct FrameRateLimiter {
+    min_interval: Option<Duration>,
+    last_emit: Option<Instant>,
+}
+
+impl FrameRateLimiter {
+    fn new(target_fps: Option<f64>) -> Self {
+        let min_interval = match target_fps {
+            Some(fps) if fps > 0.0 => Some(Duration::from_secs_f64(1.0 / fps)),
+            _ => None,
+        };
+        Self { min_interval, last_emit: None }
+    }
+
+    /// Whether the frame completing at `now` should be emitted.
+    fn should_emit(&mut self, now: Instant) -> bool {
+        let Some(interval) = self.min_interval else { return true };
+        match self.last_emit {
+            Some(last) if now.duration_since(last) < interval => false,
+            _ => {
+                self.last_emit = Some(now);
+                true
+            }
+        }
+    }
+}
+
+/// Parse the frame number from a `NNNNNNNN.dsql` capture filename.
+///
+/// Returns `None` for names that are not an 8-hex-digit frame number with the
+/// `.dsql` extension. Shared by [`SimpleFrameGrabber`]'s resume logic and the
+/// replayer's file discovery so both agree on the naming scheme.
+pub(crate) fn parse_dsql_frame_number(filename: &str) -> Option<u32> {
+    if !filename.ends_with(".dsql") {
+        return None;
+    }
+    u32::from_str_radix(filename.get(..8)?, 16).ok()
+}
+
+/// Number of datagram-sized slots in the shared capture ring.
+const RING_SLOT_COUNT: usize = 10_000;
+/// Size of each ring slot in bytes (matches the UDP receive buffer).
+const RING_SLOT_SIZE: usize = 4096;
 
 /// Simple framegrabber that matches the C implementation approach:
 /// 1. UDP receiver thread: UDP packets -> continuous buffer
@@ -27,6 +84,22 @@ use crate::frame_sync::FrameSyncEngine;
 /// 5. Support for debug mode (generate synthetic LiDAR data)
 /// 6. Support for decode mode (output decoded coordinates instead of raw HWORD data)
 /// 7. Each capture session creates a timestamped subdirectory
+/// Snapshot of capture progress published to a live consumer (e.g. the GUI).
+///
+/// Sent over a bounded channel each time a frame completes so the consumer can
+/// refresh its counters and redraw a preview without touching the capture
+/// threads. The channel is bounded and sends are non-blocking, so a slow
+/// consumer never stalls capture — stale snapshots are simply dropped.
+#[derive(Debug, Clone)]
+pub struct CaptureStats {
+    /// Total datagrams received so far.
+    pub packet_count: u64,
+    /// Total frames completed so far.
+    pub frame_count: u64,
+    /// Decoded coordinates of the most recent frame, if decoded.
+    pub latest: Option<CoordinateData>,
+}
+
 pub struct SimpleFrameGrabber {
     bind_addr: String,
     port: u16,
@@ -36,9 +109,63 @@ pub struct SimpleFrameGrabber {
     live_output: bool,
     debug_mode: bool,
     decode_mode: bool,
+    /// Number of datagrams to pull per `recvmmsg` syscall (1 = legacy `recv`).
+    batch_size: usize,
+    /// Optional CPU-core pinning for the capture threads.
+    affinity: CoreAffinity,
+    /// Coordinate transform pipeline applied to decoded points before output.
+    transform_chain: Arc<TransformChain>,
+    /// Optional target output frame rate; whole frames are dropped to hit it.
+    output_fps: Option<f64>,
+    /// Point decimation factor passed to `extract_coordinates` in decode mode.
+    decimation: usize,
+    /// Which sink live output is published to (stdout by default).
+    sink_config: OutputSinkConfig,
+    /// Byte order for synthetic frame generation (and future raw decode).
+    endianness: Endianness,
+    /// Append a SHA-512 integrity trailer to generated frames (feature-gated).
+    integrity: bool,
+    /// Optional bounded channel for publishing live [`CaptureStats`].
+    stats_tx: Option<SyncSender<CaptureStats>>,
+    /// Shared running total of datagrams received, read by the stats publisher.
+    packet_counter: Arc<AtomicU64>,
+    /// Upper bound on HWORDs per frame enforced by the sync-engine watchdog.
+    max_frame_size_hwords: usize,
+    /// Per-frame inactivity timeout enforced by the sync-engine watchdog.
+    frame_timeout: Duration,
+    /// Optional zstd compression applied to stored `.dsql` frames.
+    compression: CompressionConfig,
+    /// Block codec wrapping each stored `.dsql` frame (independent of the
+    /// dictionary-trained [`compression`](Self::compression) path).
+    codec: FrameCodec,
+    /// Run the idle/header pre-pass before the block codec.
+    codec_prepass: bool,
+    /// How the sync engine reconstructs the imaging pixel count from registers.
+    pixel_count_layout: PixelCountLayout,
+    /// Register map used to read the header-declared fields of completed frames.
+    header_layout: shared::HeaderLayout,
     running: Arc<AtomicBool>,
 }
 
+/// Default number of datagrams received per `recvmmsg` syscall.
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Optional CPU-affinity assignments for the capture threads.
+///
+/// Each field is the index of a core (as enumerated by the topology probe) to
+/// pin the corresponding thread to. `None` leaves the thread unpinned, which is
+/// the default and matches the historical behavior. Pinning the UDP receiver and
+/// the file writer to separate, isolated cores keeps the writer from being
+/// descheduled behind the receiver under load — the main source of the
+/// "Ring buffer full" drops on high-rate streams.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CoreAffinity {
+    /// Core for the UDP receiver / tokio runtime thread.
+    pub receiver_core: Option<usize>,
+    /// Core for the file-writer thread.
+    pub writer_core: Option<usize>,
+}
+
 impl SimpleFrameGrabber {
     /// Create a new frame grabber with timestamped subdirectory for this session
     pub fn new(
@@ -74,10 +201,147 @@ impl SimpleFrameGrabber {
             live_output,
             debug_mode,
             decode_mode,
+            batch_size: DEFAULT_BATCH_SIZE,
+            affinity: CoreAffinity::default(),
+            transform_chain: Arc::new(TransformChain::new()),
+            output_fps: None,
+            decimation: 1,
+            sink_config: OutputSinkConfig::Stdout,
+            endianness: Endianness::Big,
+            integrity: false,
+            stats_tx: None,
+            packet_counter: Arc::new(AtomicU64::new(0)),
+            max_frame_size_hwords: 1_000_000,
+            frame_timeout: Duration::from_secs(5),
+            compression: CompressionConfig::default(),
+            codec: FrameCodec::default(),
+            codec_prepass: false,
+            pixel_count_layout: PixelCountLayout::default(),
+            header_layout: shared::HeaderLayout::default(),
             running: Arc::new(AtomicBool::new(true)),
         }
     }
-    
+
+    /// Set the sync-engine watchdog limits: the maximum HWORDs a single frame may
+    /// accumulate and the inactivity timeout after which a stalled frame is
+    /// aborted. Both are normally taken from [`crate::config::FrameGrabberConfig`].
+    pub fn set_sync_limits(&mut self, max_frame_size_hwords: usize, frame_timeout: Duration) {
+        self.max_frame_size_hwords = max_frame_size_hwords;
+        self.frame_timeout = frame_timeout;
+    }
+
+    /// Configure optional zstd compression of stored `.dsql` frames.
+    pub fn set_compression(&mut self, compression: CompressionConfig) {
+        self.compression = compression;
+    }
+
+    /// Select the block codec applied to stored frames, and whether the
+    /// idle/header pre-pass runs before it. [`FrameCodec::None`] with the
+    /// pre-pass off persists raw HWORD buffers, matching the historical format.
+    pub fn set_codec(&mut self, codec: FrameCodec, prepass: bool) {
+        self.codec = codec;
+        self.codec_prepass = prepass;
+    }
+
+    /// Select how the sync engine reconstructs the imaging pixel count from the
+    /// header registers (see [`PixelCountLayout`]).
+    pub fn set_pixel_count_layout(&mut self, layout: PixelCountLayout) {
+        self.pixel_count_layout = layout;
+    }
+
+    /// Set the register map used to read the header-declared fields (expected
+    /// pixel count, width, height, scan mode) of completed frames.
+    ///
+    /// Normally taken from [`crate::config::FrameGrabberConfig::header_layout`]
+    /// so a firmware revision with a non-reference header layout is decoded
+    /// correctly without recompiling.
+    pub fn set_header_layout(&mut self, layout: shared::HeaderLayout) {
+        self.header_layout = layout;
+    }
+
+    /// Publish live [`CaptureStats`] over `tx` as frames complete.
+    ///
+    /// The sender should be the producer end of a bounded channel; the capture
+    /// path never blocks on it, so a backed-up consumer just misses snapshots.
+    pub fn set_stats_sender(&mut self, tx: SyncSender<CaptureStats>) {
+        self.stats_tx = Some(tx);
+    }
+
+    /// Set how many datagrams are pulled per `recvmmsg` syscall.
+    ///
+    /// A value of `1` falls back to the classic one-`recv`-per-datagram path.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.max(1);
+    }
+
+    /// Pin the receiver and writer threads to dedicated CPU cores.
+    ///
+    /// Unset (`None`) fields leave that thread unpinned. See [`CoreAffinity`].
+    pub fn set_core_affinity(&mut self, affinity: CoreAffinity) {
+        self.affinity = affinity;
+    }
+
+    /// Install the coordinate [`TransformChain`] applied to decoded points in
+    /// decode mode before they are emitted or saved.
+    ///
+    /// The chain runs after field extraction and before JSON emission, so both
+    /// streamed and stored data is already in the target frame. An empty chain
+    /// (the default) leaves decoded coordinates untouched.
+    pub fn set_transform_chain(&mut self, chain: TransformChain) {
+        self.transform_chain = Arc::new(chain);
+    }
+
+    /// Limit output to `fps` frames per second, dropping whole frames to hit the
+    /// rate and keeping the most recent. `None` emits every completed frame.
+    pub fn set_output_fps(&mut self, fps: Option<f64>) {
+        self.output_fps = fps;
+    }
+
+    /// Set the point decimation factor applied when extracting decode-mode
+    /// coordinates (`1` = every point, `n` = every nth point).
+    pub fn set_decimation(&mut self, factor: usize) {
+        self.decimation = factor.max(1);
+    }
+
+    /// Select the sink live output is published to (stdout by default).
+    pub fn set_output_sink(&mut self, sink_config: OutputSinkConfig) {
+        self.sink_config = sink_config;
+    }
+
+    /// Set the byte order used to generate synthetic frames (`Big` by default).
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Append a SHA-512 integrity trailer to generated frames. Requires the
+    /// `integrity` feature; a no-op otherwise.
+    pub fn set_integrity(&mut self, integrity: bool) {
+        self.integrity = integrity;
+    }
+
+    /// Pin the calling thread to `core` using the platform topology probe.
+    ///
+    /// `None` leaves the thread unpinned. An out-of-range index or a kernel that
+    /// refuses the affinity call is logged and otherwise ignored — pinning is a
+    /// best-effort optimization, never a correctness requirement.
+    fn pin_current_thread(core: Option<usize>, label: &str) {
+        let Some(core) = core else { return };
+        let Some(core_ids) = core_affinity::get_core_ids() else {
+            warn!("Could not probe CPU topology; leaving {} thread unpinned", label);
+            return;
+        };
+        match core_ids.get(core) {
+            Some(&core_id) if core_affinity::set_for_current(core_id) => {
+                info!("📌 Pinned {} thread to core {}", label, core);
+            }
+            Some(_) => warn!("Kernel refused to pin {} thread to core {}", label, core),
+            None => warn!(
+                "Core {} out of range (machine has {} cores); leaving {} thread unpinned",
+                core, core_ids.len(), label
+            ),
+        }
+    }
+
     /// Get a reference to the running flag for external control
     pub fn get_running_flag(&self) -> Arc<AtomicBool> {
         Arc::clone(&self.running)
@@ -85,9 +349,12 @@ impl SimpleFrameGrabber {
 
     /// Start the frame capture process
     pub fn start(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Create channel for raw UDP data (like C version's buffer)
-        // Increased from 1000 to 10000 to handle high-speed UDP streams without dropping packets
-        let (data_tx, data_rx) = bounded::<Vec<u8>>(10000);
+        // Preallocated, zero-copy ring shared between the receiver and writer
+        // threads. Replaces the per-packet `Vec` channel that allocated on every
+        // datagram and dropped packets when the writer fell behind.
+        let ring = Arc::new(GulpRing::new(RING_SLOT_COUNT, RING_SLOT_SIZE));
+        let ring_producer = Arc::clone(&ring);
+        let ring_consumer = ring;
 
         // Clone for threads
         let running_udp = Arc::clone(&self.running);
@@ -95,16 +362,36 @@ impl SimpleFrameGrabber {
         let bind_addr = self.bind_addr.clone();
         let port = self.port;
         let timestamped_output_dir = self.timestamped_output_dir.clone();
+        let batch_size = self.batch_size;
+        let receiver_core = self.affinity.receiver_core;
+        let writer_core = self.affinity.writer_core;
         let save_files = self.save_files;
         let live_output = self.live_output;
         let debug_mode = self.debug_mode;
         let decode_mode = self.decode_mode;
+        let transform_chain = Arc::clone(&self.transform_chain);
+        let output_fps = self.output_fps;
+        let decimation = self.decimation;
+        let sink_config = self.sink_config.clone();
+        let endianness = self.endianness;
+        let integrity = self.integrity;
+        let stats_tx = self.stats_tx.clone();
+        let max_frame_size_hwords = self.max_frame_size_hwords;
+        let frame_timeout = self.frame_timeout;
+        let compression = self.compression.clone();
+        let codec = self.codec;
+        let codec_prepass = self.codec_prepass;
+        let pixel_count_layout = self.pixel_count_layout;
+        let header_layout = self.header_layout;
+        let packet_counter_rx = Arc::clone(&self.packet_counter);
+        let packet_counter_writer = Arc::clone(&self.packet_counter);
 
         // Start data source thread (UDP receiver or debug generator)
         let udp_handle = if debug_mode {
             // Debug mode: generate synthetic data
             thread::spawn(move || {
-                match Self::debug_data_generator_thread(data_tx, running_udp) {
+                Self::pin_current_thread(receiver_core, "debug generator");
+                match Self::debug_data_generator_thread(ring_producer, running_udp, endianness, integrity) {
                     Ok(_) => {
                         info!("Debug data generator thread finished successfully");
                         Ok(())
@@ -118,9 +405,10 @@ impl SimpleFrameGrabber {
         } else {
             // Normal mode: UDP receiver
             thread::spawn(move || {
+                Self::pin_current_thread(receiver_core, "UDP receiver");
                 let rt = Runtime::new().unwrap();
                 rt.block_on(async {
-                    match Self::udp_receiver_thread(bind_addr, port, data_tx, running_udp).await {
+                    match Self::udp_receiver_thread(bind_addr, port, batch_size, ring_producer, running_udp, packet_counter_rx).await {
                         Ok(_) => {
                             info!("UDP receiver thread finished successfully");
                             Ok(())
@@ -136,7 +424,8 @@ impl SimpleFrameGrabber {
 
         // Start file writer thread (matches C's thFrameCap)
         let writer_handle = thread::spawn(move || {
-            match Self::file_writer_thread(timestamped_output_dir, data_rx, running_writer, save_files, live_output, decode_mode) {
+            Self::pin_current_thread(writer_core, "file writer");
+            match Self::file_writer_thread(timestamped_output_dir, ring_consumer, running_writer, save_files, live_output, decode_mode, transform_chain, output_fps, decimation, sink_config, stats_tx, packet_counter_writer, max_frame_size_hwords, frame_timeout, compression, codec, codec_prepass, pixel_count_layout, header_layout) {
                 Ok(_) => {
                     info!("File writer thread finished successfully");
                     Ok(())
@@ -162,8 +451,10 @@ impl SimpleFrameGrabber {
     async fn udp_receiver_thread(
         bind_addr: String,
         port: u16,
-        data_tx: Sender<Vec<u8>>,
+        batch_size: usize,
+        ring: Arc<GulpRing>,
         running: Arc<AtomicBool>,
+        packet_counter: Arc<AtomicU64>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let socket_addr = format!("{}:{}", bind_addr, port);
         info!("🔌 Binding UDP socket to {}", socket_addr);
@@ -176,7 +467,11 @@ impl SimpleFrameGrabber {
         sock_ref.set_recv_buffer_size(1024 * 1024)?; // 1MB like C version
         info!("📊 Socket receive buffer set to 1MB");
 
-        let mut buffer = vec![0u8; 4096]; // Match C's FG_UDP_BUFLEN
+        if batch_size > 1 {
+            info!("📦 Batched reception enabled: up to {} datagrams per recvmmsg", batch_size);
+            return Self::udp_receiver_batched(socket, batch_size, ring, running, packet_counter).await;
+        }
+
         let mut total_bytes = 0u64;
         let mut packet_count = 0u64;
         let mut error_count = 0u64;
@@ -185,19 +480,27 @@ impl SimpleFrameGrabber {
         while running.load(Ordering::SeqCst) {
             // Set a timeout to check the running flag periodically
             let timeout = Duration::from_millis(100);
-            
-            match tokio::time::timeout(timeout, socket.recv(&mut buffer)).await {
+
+            // Reserve the next free ring slot and receive straight into it, so
+            // the datagram is never copied into a throwaway `Vec`.
+            let Some(mut slot) = ring.reserve() else {
+                // Ring full: the writer can't keep up. Count the overrun and
+                // retry rather than allocating or silently discarding.
+                error!("Ring buffer full, applying backpressure! The file writer can't keep up.");
+                error_count += 1;
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                continue;
+            };
+
+            match tokio::time::timeout(timeout, socket.recv(slot.buf())).await {
                 Ok(Ok(bytes_received)) => {
                     if bytes_received > 0 {
                         total_bytes += bytes_received as u64;
                         packet_count += 1;
+                        packet_counter.fetch_add(1, Ordering::Relaxed);
 
-                        // Send raw data to writer thread (like C's pipe/buffer)
-                        let packet_data = buffer[..bytes_received].to_vec();
-                        if let Err(_) = data_tx.try_send(packet_data) {
-                            error!("Data channel full, dropping packet! This indicates the file writer can't keep up.");
-                            error_count += 1;
-                        }
+                        // Publish the filled slot to the writer thread.
+                        slot.commit(bytes_received);
 
                         // Log progress every 1000 packets
                         if packet_count % 1000 == 0 {
@@ -232,6 +535,140 @@ impl SimpleFrameGrabber {
         Ok(())
     }
 
+    /// Batched UDP receive path: pulls up to `batch_size` datagrams per
+    /// `recvmmsg` syscall into preallocated buffers, then forwards each one to
+    /// the writer through the ring. Falls back to the same 100 ms `running`-flag
+    /// poll as the single-datagram path when the socket is idle.
+    async fn udp_receiver_batched(
+        socket: UdpSocket,
+        batch_size: usize,
+        ring: Arc<GulpRing>,
+        running: Arc<AtomicBool>,
+        packet_counter: Arc<AtomicU64>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use std::os::unix::io::AsRawFd;
+
+        let fd = socket.as_raw_fd();
+        let slot_size = ring.slot_size();
+
+        // Preallocated receive buffers reused across every syscall — one per
+        // message slot in the batch.
+        let mut buffers: Vec<Vec<u8>> = (0..batch_size).map(|_| vec![0u8; slot_size]).collect();
+
+        let mut total_bytes = 0u64;
+        let mut packet_count = 0u64;
+        let mut error_count = 0u64;
+        let start_time = Instant::now();
+
+        while running.load(Ordering::SeqCst) {
+            // Wait for the socket to become readable, re-checking the running
+            // flag every 100 ms so shutdown stays responsive.
+            let ready = tokio::time::timeout(Duration::from_millis(100), socket.readable()).await;
+            match ready {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    error!("UDP readiness error: {}", e);
+                    error_count += 1;
+                    continue;
+                }
+                Err(_) => continue, // timeout — re-check running flag
+            }
+
+            // Build the `mmsghdr`/`iovec` scratch arrays over the preallocated
+            // buffers. These are rebuilt each pass because they borrow the
+            // buffers mutably; the buffers themselves are never reallocated.
+            let mut iovecs: Vec<libc::iovec> = buffers
+                .iter_mut()
+                .map(|b| libc::iovec {
+                    iov_base: b.as_mut_ptr() as *mut libc::c_void,
+                    iov_len: slot_size,
+                })
+                .collect();
+            let mut msgs: Vec<libc::mmsghdr> = iovecs
+                .iter_mut()
+                .map(|iov| {
+                    // SAFETY: `msghdr` is plain old data; zeroing it and setting
+                    // the iovec pointer/count is the documented recvmmsg setup.
+                    let mut hdr: libc::msghdr = unsafe { core::mem::zeroed() };
+                    hdr.msg_iov = iov as *mut libc::iovec;
+                    hdr.msg_iovlen = 1;
+                    libc::mmsghdr { msg_hdr: hdr, msg_len: 0 }
+                })
+                .collect();
+
+            // Drain whatever is ready in one non-blocking syscall. `try_io`
+            // clears tokio's readiness if the kernel returns EWOULDBLOCK.
+            let received = socket.try_io(tokio::io::Interest::READABLE, || {
+                // SAFETY: `msgs` points at `batch_size` initialised headers, each
+                // referencing a live buffer of `slot_size` bytes.
+                let n = unsafe {
+                    libc::recvmmsg(
+                        fd,
+                        msgs.as_mut_ptr(),
+                        batch_size as libc::c_uint,
+                        libc::MSG_DONTWAIT,
+                        core::ptr::null_mut(),
+                    )
+                };
+                if n < 0 {
+                    Err(std::io::Error::last_os_error())
+                } else {
+                    Ok(n as usize)
+                }
+            });
+
+            let count = match received {
+                Ok(count) => count,
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => continue,
+                Err(e) => {
+                    error!("recvmmsg error: {}", e);
+                    error_count += 1;
+                    continue;
+                }
+            };
+
+            for (idx, msg) in msgs.iter().take(count).enumerate() {
+                let len = msg.msg_len as usize;
+                if len == 0 {
+                    continue;
+                }
+                let mut slot = loop {
+                    if let Some(slot) = ring.reserve() {
+                        break slot;
+                    }
+                    error!("Ring buffer full, applying backpressure! The file writer can't keep up.");
+                    error_count += 1;
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                };
+                let copy = len.min(slot_size);
+                slot.buf()[..copy].copy_from_slice(&buffers[idx][..copy]);
+                slot.commit(copy);
+
+                total_bytes += copy as u64;
+                packet_count += 1;
+                packet_counter.fetch_add(1, Ordering::Relaxed);
+
+                if packet_count % 1000 == 0 {
+                    let elapsed = start_time.elapsed();
+                    let rate_mbps = (total_bytes as f64 * 8.0) / (elapsed.as_secs_f64() * 1_000_000.0);
+                    info!("📊 Received {} packets, {:.1} MB, {:.2} Mbps, {} errors",
+                          packet_count, total_bytes as f64 / 1_000_000.0, rate_mbps, error_count);
+                }
+            }
+        }
+
+        let elapsed = start_time.elapsed();
+        let rate_mbps = (total_bytes as f64 * 8.0) / (elapsed.as_secs_f64() * 1_000_000.0);
+        info!("📈 UDP receiver final stats:");
+        info!("   Packets: {}", packet_count);
+        info!("   Bytes: {:.1} MB", total_bytes as f64 / 1_000_000.0);
+        info!("   Rate: {:.2} Mbps", rate_mbps);
+        info!("   Errors: {}", error_count);
+        info!("   Duration: {:.1}s", elapsed.as_secs_f64());
+
+        Ok(())
+    }
+
     /// Find the next available frame number by checking existing files
     fn find_next_frame_number(output_dir: &str) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
         let mut max_frame = 0u32;
@@ -241,12 +678,10 @@ impl SimpleFrameGrabber {
                 if let Ok(metadata) = entry.metadata() {
                     if metadata.is_file() {
                         if let Some(filename) = entry.file_name().to_str() {
-                            if filename.ends_with(".dsql") {
-                                // Try to parse the frame number from the filename (e.g., "00000001.dsql")
-                                if let Ok(frame_num) = u32::from_str_radix(&filename[..8], 16) {
-                                    if frame_num >= max_frame {
-                                        max_frame = frame_num + 1;
-                                    }
+                            // Try to parse the frame number from the filename (e.g., "00000001.dsql")
+                            if let Some(frame_num) = parse_dsql_frame_number(filename) {
+                                if frame_num >= max_frame {
+                                    max_frame = frame_num + 1;
                                 }
                             }
                         }
@@ -262,11 +697,24 @@ impl SimpleFrameGrabber {
     /// Uses count-based frame synchronization instead of signature-based detection
     fn file_writer_thread(
         output_dir: String,
-        data_rx: Receiver<Vec<u8>>,
+        ring: Arc<GulpRing>,
         running: Arc<AtomicBool>,
         save_files: bool,
         live_output: bool,
         decode_mode: bool,
+        transform_chain: Arc<TransformChain>,
+        output_fps: Option<f64>,
+        decimation: usize,
+        sink_config: OutputSinkConfig,
+        stats_tx: Option<SyncSender<CaptureStats>>,
+        packet_counter: Arc<AtomicU64>,
+        max_frame_size_hwords: usize,
+        frame_timeout: Duration,
+        compression: CompressionConfig,
+        codec: FrameCodec,
+        codec_prepass: bool,
+        pixel_count_layout: PixelCountLayout,
+        header_layout: shared::HeaderLayout,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Create timestamped output directory if saving files
         if save_files {
@@ -289,21 +737,40 @@ impl SimpleFrameGrabber {
         let mut total_hwords_processed = 0u64;
         let mut file_write_errors = 0u64;
 
-        // Create frame synchronization engine
-        let mut sync_engine = FrameSyncEngine::new();
+        // Create frame synchronization engine with the watchdog limits so a
+        // corrupted stream can't grow a frame buffer without bound.
+        let mut sync_engine =
+            FrameSyncEngine::with_config(max_frame_size_hwords, frame_timeout, pixel_count_layout);
+
+        // Optional zstd compressor for stored frames. Built once so a trained
+        // dictionary is reused across every frame in the session.
+        let mut compressor = if compression.enabled {
+            Some(FrameCompressor::new(&compression)?)
+        } else {
+            None
+        };
+
+        // Output rate limiter: drops whole frames to hit the target FPS.
+        let mut rate_limiter = FrameRateLimiter::new(output_fps);
+
+        // Build the live-output sink up front so a bad shared-memory config fails
+        // before capture starts rather than mid-stream.
+        let mut sink = if live_output {
+            Some(sink_config.build(Arc::clone(&transform_chain), decimation)?)
+        } else {
+            None
+        };
 
         info!("📝 File writer thread started (save_files: {}, live_output: {}, decode_mode: {})", save_files, live_output, decode_mode);
         info!("🔧 Using count-based frame synchronization");
 
-        while running.load(Ordering::SeqCst) || !data_rx.is_empty() {
-            // Receive data with timeout
-            match data_rx.recv_timeout(Duration::from_millis(100)) {
-                Ok(mut packet_data) => {
-                    // Append to continuous buffer
-                    buffer.append(&mut packet_data);
-
-                    // Process 12-byte chunks using count-based synchronization
-                    while buffer.len() >= 12 {
+        while running.load(Ordering::SeqCst) || ring.has_data() {
+            // Pull the next published slot straight out of the ring memory. When
+            // the ring is momentarily empty, park briefly and re-check the flag.
+            let got_data = ring.read_with(|bytes| buffer.extend_from_slice(bytes));
+            if got_data.is_some() {
+                // Process 12-byte chunks using count-based synchronization
+                while buffer.len() >= 12 {
                         // Extract 12-byte chunk
                         let chunk: [u8; 12] = buffer.drain(0..12)
                             .collect::<Vec<u8>>()
@@ -312,43 +779,108 @@ impl SimpleFrameGrabber {
 
                         total_hwords_processed += 1;
 
-                        // Process HWORD through synchronization engine
-                        if let Some(frame_data) = sync_engine.process_hword(&chunk) {
+                        // Process HWORD through synchronization engine. The
+                        // timestamped variant lets the watchdog abort frames that
+                        // stall or overrun `max_frame_size_hwords`.
+                        if let Some((frame_data, _quality)) = sync_engine.process_hword_at(&chunk, Instant::now()) {
                             // Frame complete! Write it immediately
                             let hwords_in_frame = frame_data.len() / 12;
 
+                            // Drop whole frames to honor the target output rate.
+                            // The capture loop keeps running at full speed; only
+                            // the output stage is throttled.
+                            if !rate_limiter.should_emit(Instant::now()) {
+                                frame_counter += 1;
+                                continue;
+                            }
+
                             // Handle live output if enabled
-                            if live_output {
-                                Self::output_live_frame(&frame_data, frame_counter, hwords_in_frame, decode_mode)?;
+                            if let Some(sink) = sink.as_mut() {
+                                sink.write_frame(frame_counter, &frame_data, decode_mode)?;
                             }
 
                             // Handle file saving if enabled
                             if save_files {
                                 let filename = format!("{}/{:08X}.dsql", output_dir, frame_counter);
-                                match std::fs::write(&filename, &frame_data) {
-                                    Ok(_) => {
-                                        info!("✅ Completed frame file: {} ({} HWORDs, {:.1} KB)",
-                                              filename, hwords_in_frame, frame_data.len() as f64 / 1024.0);
-                                    }
-                                    Err(e) => {
-                                        error!("❌ Failed to write frame file {}: {}", filename, e);
+                                // Wrap the frame for storage. The dictionary
+                                // compressor takes precedence; otherwise the
+                                // selectable block codec runs (a no-op that keeps
+                                // the raw buffer when the codec is `None` and the
+                                // pre-pass is off).
+                                let encoding = if compressor.is_some() {
+                                    compressor.as_mut().map(|c| c.compress_frame(&frame_data))
+                                } else if codec != FrameCodec::None || codec_prepass {
+                                    Some(crate::codec::encode(codec, codec_prepass, &frame_data))
+                                } else {
+                                    None
+                                };
+                                let record = match encoding {
+                                    Some(Ok(record)) => Some(record),
+                                    Some(Err(e)) => {
+                                        error!("❌ Failed to encode frame {}: {}", frame_counter, e);
                                         file_write_errors += 1;
+                                        None
+                                    }
+                                    None => None,
+                                };
+                                // Encoded records carry a header; raw frames are
+                                // written straight from the buffer to avoid a copy.
+                                let bytes: &[u8] = record.as_deref().unwrap_or(&frame_data);
+                                let encoding_attempted =
+                                    compressor.is_some() || codec != FrameCodec::None || codec_prepass;
+                                if !encoding_attempted || record.is_some() {
+                                    match std::fs::write(&filename, bytes) {
+                                        Ok(_) => {
+                                            info!("✅ Completed frame file: {} ({} HWORDs, {:.1} KB)",
+                                                  filename, hwords_in_frame, bytes.len() as f64 / 1024.0);
+                                        }
+                                        Err(e) => {
+                                            error!("❌ Failed to write frame file {}: {}", filename, e);
+                                            file_write_errors += 1;
+                                        }
                                     }
                                 }
                             }
 
                             frame_counter += 1;
+
+                            // Decode the completed frame once for header
+                            // validation and any live stats consumer.
+                            let decoded = Frame::from_bytes(frame_counter, &frame_data).ok();
+
+                            // Compare the header-declared pixel count against what
+                            // was collected, reading the declared count through the
+                            // configured register map so non-reference firmware
+                            // layouts are validated correctly. A mismatch flags a
+                            // truncated or padded frame without aborting capture.
+                            if let Some(frame) = decoded.as_ref() {
+                                let declared = frame.num_pixels_with_layout(&header_layout);
+                                let actual = frame.pixels.len();
+                                if declared != actual {
+                                    warn!("Frame {}: header declares {} pixels but {} were collected",
+                                          frame_counter, declared, actual);
+                                }
+                            }
+
+                            // Publish a stats snapshot for any live consumer. The
+                            // send is non-blocking, so a slow consumer just misses
+                            // this frame rather than stalling capture.
+                            if let Some(tx) = stats_tx.as_ref() {
+                                let latest = decoded
+                                    .as_ref()
+                                    .map(|frame| frame.data(Some(decimation), None, None));
+                                let snapshot = CaptureStats {
+                                    packet_count: packet_counter.load(Ordering::Relaxed),
+                                    frame_count: frame_counter as u64,
+                                    latest,
+                                };
+                                let _ = tx.try_send(snapshot);
+                            }
                         }
-                    }
-                }
-                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
-                    // Timeout - continue to check running flag
-                    continue;
-                }
-                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
-                    info!("Data channel disconnected - UDP receiver has stopped");
-                    break;
                 }
+            } else {
+                // Ring empty - park briefly, then re-check the running flag.
+                thread::sleep(Duration::from_millis(1));
             }
         }
 
@@ -387,11 +919,13 @@ impl SimpleFrameGrabber {
     }
 
     /// Output frame data to stdout for live processing
-    fn output_live_frame(
+    pub(crate) fn output_live_frame(
         hword_buffer: &[u8],
         frame_counter: u32,
         hwords_in_frame: usize,
         decode_mode: bool,
+        transform_chain: &TransformChain,
+        decimation: usize,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         use std::io::{self, Write};
 
@@ -399,22 +933,21 @@ impl SimpleFrameGrabber {
             // Decode the HWORD data and output JSON coordinates
             match Frame::from_bytes(frame_counter, hword_buffer) {
                 Ok(frame) => {
-                    // Extract coordinates with all fields
+                    // Extract coordinates with all fields, applying the
+                    // configured point decimation factor.
                     let whitelist = FieldWhitelist::all();
-                    let coordinates = frame.pixels.extract_coordinates(&whitelist, 1); // No decimation
-
-                    // Convert coordinates to arrays
-                    let mut x_coords = Vec::new();
-                    let mut y_coords = Vec::new();
-                    let mut z_coords = Vec::new();
-                    let mut intensities = Vec::new();
-
-                    for point in &coordinates.points {
-                        x_coords.push(point.x.unwrap_or(0.0));
-                        y_coords.push(point.y.unwrap_or(0.0));
-                        z_coords.push(point.z.unwrap_or(0.0));
-                        intensities.push(point.intensity.unwrap_or(0));
-                    }
+                    let mut coordinates = frame.pixels.extract_coordinates(&whitelist, decimation, None);
+
+                    // Apply the configured transform pipeline so streamed data is
+                    // already in the target frame. A no-op when the chain is empty.
+                    transform_chain.apply(&mut coordinates);
+
+                    // Convert coordinates to arrays from the contiguous columns.
+                    let x_coords = coordinates.x_values().map(<[f64]>::to_vec).unwrap_or_default();
+                    let y_coords = coordinates.y_values().map(<[f64]>::to_vec).unwrap_or_default();
+                    let z_coords = coordinates.z_values().map(<[f64]>::to_vec).unwrap_or_default();
+                    let intensities =
+                        coordinates.intensity_values().map(<[u16]>::to_vec).unwrap_or_default();
 
                     // Create JSON output
                     let json_output = serde_json::json!({
@@ -455,8 +988,10 @@ impl SimpleFrameGrabber {
 
     /// Debug data generator thread - generates synthetic LiDAR data
     fn debug_data_generator_thread(
-        data_tx: Sender<Vec<u8>>,
+        ring: Arc<GulpRing>,
         running: Arc<AtomicBool>,
+        endianness: Endianness,
+        integrity: bool,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("🧪 Starting debug data generator");
 
@@ -469,12 +1004,22 @@ impl SimpleFrameGrabber {
 
             // Generate frame at specified interval
             if now.duration_since(last_frame_time) >= frame_interval {
-                let synthetic_frame = Self::generate_synthetic_frame(frame_counter);
-
-                // Send synthetic frame data
-                if let Err(e) = data_tx.try_send(synthetic_frame) {
-                    warn!("Failed to send synthetic frame data: {}", e);
-                    break;
+                let synthetic_frame = Self::generate_synthetic_frame(frame_counter, endianness, integrity);
+
+                // Feed the synthetic frame into the ring in slot-sized chunks,
+                // blocking on backpressure rather than dropping it.
+                for chunk in synthetic_frame.chunks(ring.slot_size()) {
+                    loop {
+                        if !running.load(Ordering::SeqCst) {
+                            return Ok(());
+                        }
+                        if let Some(mut slot) = ring.reserve() {
+                            slot.buf()[..chunk.len()].copy_from_slice(chunk);
+                            slot.commit(chunk.len());
+                            break;
+                        }
+                        thread::sleep(Duration::from_millis(1));
+                    }
                 }
 
                 frame_counter += 1;
@@ -494,7 +1039,7 @@ impl SimpleFrameGrabber {
     }
 
     /// Generate a synthetic LiDAR frame with realistic point cloud data
-    fn generate_synthetic_frame(frame_number: u32) -> Vec<u8> {
+    fn generate_synthetic_frame(frame_number: u32, endianness: Endianness, integrity: bool) -> Vec<u8> {
         use std::f32::consts::PI;
 
         // Generate a synthetic point cloud that looks like LiDAR data
@@ -506,20 +1051,14 @@ impl SimpleFrameGrabber {
         let first_header_data: u128 = (frame_number as u128) | ((points_per_frame as u128) << 32); // Frame number and pixel count
         let first_header_control = 2u8; // FirstHeader (010)
         let first_header_word = ((first_header_control as u128) << 93) | first_header_data;
-        let mut first_header_hword = [0u8; 12];
-        for j in 0..12 {
-            first_header_hword[j] = ((first_header_word >> (88 - j * 8)) & 0xFF) as u8;
-        }
+        let first_header_hword = endianness.write_word96(first_header_word);
         frame_data.extend_from_slice(&first_header_hword);
 
         // SubsequentHeader HWORD (optional but good for completeness)
         let subsequent_header_data: u128 = 0; // Additional header data if needed
         let subsequent_header_control = 3u8; // SubsequentHeader (011)
         let subsequent_header_word = ((subsequent_header_control as u128) << 93) | subsequent_header_data;
-        let mut subsequent_header_hword = [0u8; 12];
-        for j in 0..12 {
-            subsequent_header_hword[j] = ((subsequent_header_word >> (88 - j * 8)) & 0xFF) as u8;
-        }
+        let subsequent_header_hword = endianness.write_word96(subsequent_header_word);
         frame_data.extend_from_slice(&subsequent_header_hword);
 
         // Generate points in a realistic pattern
@@ -577,15 +1116,25 @@ impl SimpleFrameGrabber {
             // Construct the full 96-bit word
             let word_96bit = ((control_bits as u128) << 93) | (parity_bit << 92) | data_92bit;
 
-            // Convert to 12 bytes (big-endian as expected by the parser)
-            let mut hword = [0u8; 12];
-            for j in 0..12 {
-                hword[j] = ((word_96bit >> (88 - j * 8)) & 0xFF) as u8;
-            }
+            // Serialize the word in the configured byte order so the generator
+            // can emulate little-endian capture devices as well as the native
+            // big-endian firmware.
+            let hword = endianness.write_word96(word_96bit);
 
             frame_data.extend_from_slice(&hword);
         }
 
+        // Optionally append the SHA-512 integrity trailer computed over the
+        // pixel payloads, so the parser can verify the frame end-to-end.
+        #[cfg(feature = "integrity")]
+        if integrity {
+            if let Ok(frame) = Frame::from_bytes_with_order(frame_number, &frame_data, endianness) {
+                frame_data.extend_from_slice(&frame.integrity_digest());
+            }
+        }
+        #[cfg(not(feature = "integrity"))]
+        let _ = integrity;
+
         info!("🧪 Generated synthetic frame {} with {} points ({} bytes)",
               frame_number, points_per_frame, frame_data.len());
 
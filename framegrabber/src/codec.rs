@@ -0,0 +1,383 @@
+/*!
+Pluggable compression of stored `.dsql` frames.
+
+High-rate LiDAR capture produces `.dsql` files dominated by idle HWORDs and
+slowly-varying header registers, so the raw buffers the writer persists are
+highly redundant. [`FrameCodec`] wraps each frame in a generic block codec
+(`zstd`, `lz4`, or none) behind an 8-byte file header that records which codec
+and format version produced the record, so a reader — [`decode`] here, or any
+downstream tool — can pick the matching decoder without guessing.
+
+Before the generic codec runs, an optional domain-specific pre-pass
+([`prepass::encode`]) run-length-encodes consecutive [`ControlBits::Idle`]
+HWORDs and delta-encodes each header HWORD in a run against the word before
+it. Idle runs and the small register deltas between adjacent header words both
+collapse to a handful of bytes, which the block codec then compresses further.
+
+A file with no `DSQL` magic is a legacy raw-HWORD capture; [`decode`] passes it
+through untouched, so replaying a mixed session of old and new files just works.
+*/
+
+use std::io;
+
+use shared::hword::ControlBits;
+use shared::leb128::{decode_unsigned, encode_unsigned};
+
+/// Block codec applied to stored frames.
+///
+/// `None` persists the (optionally pre-passed) buffer verbatim; `Zstd` and
+/// `Lz4` run the respective generic block codec over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameCodec {
+    /// Store frames uncompressed (historical behavior).
+    #[default]
+    None,
+    /// Compress each frame with zstd.
+    Zstd,
+    /// Compress each frame with lz4.
+    Lz4,
+}
+
+impl FrameCodec {
+    /// On-disk identifier written into the record header.
+    fn id(self) -> u8 {
+        match self {
+            FrameCodec::None => 0,
+            FrameCodec::Zstd => 1,
+            FrameCodec::Lz4 => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(FrameCodec::None),
+            1 => Some(FrameCodec::Zstd),
+            2 => Some(FrameCodec::Lz4),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for FrameCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" | "off" => Ok(FrameCodec::None),
+            "zstd" => Ok(FrameCodec::Zstd),
+            "lz4" => Ok(FrameCodec::Lz4),
+            other => Err(format!("unknown codec '{other}' (expected none, zstd, or lz4)")),
+        }
+    }
+}
+
+/// Magic prefixing every codec-written `.dsql` file.
+const MAGIC: [u8; 4] = *b"DSQL";
+/// Record format version carried in the header.
+const FORMAT_VERSION: u8 = 1;
+/// Fixed header length: magic + version + codec + prepass + reserved.
+const HEADER_LEN: usize = 8;
+
+/// zstd level used when the codec is [`FrameCodec::Zstd`].
+const ZSTD_LEVEL: i32 = 3;
+
+/// Encode one completed frame into a codec record: an 8-byte header followed by
+/// the `codec`-compressed payload. When `prepass` is set, the idle/header
+/// pre-pass runs before the block codec.
+pub fn encode(codec: FrameCodec, prepass: bool, frame: &[u8]) -> io::Result<Vec<u8>> {
+    let staged = if prepass {
+        prepass::encode(frame)
+    } else {
+        frame.to_vec()
+    };
+
+    let payload = match codec {
+        FrameCodec::None => staged,
+        FrameCodec::Zstd => zstd::bulk::compress(&staged, ZSTD_LEVEL)?,
+        FrameCodec::Lz4 => lz4_flex::block::compress_prepend_size(&staged),
+    };
+
+    let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+    record.extend_from_slice(&MAGIC);
+    record.push(FORMAT_VERSION);
+    record.push(codec.id());
+    record.push(prepass as u8);
+    record.push(0); // reserved
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+/// Decode a stored `.dsql` record back to the raw HWORD buffer.
+///
+/// A buffer without the `DSQL` magic is treated as a legacy raw-HWORD capture
+/// and returned unchanged, so old and new files can be replayed side by side.
+pub fn decode(record: &[u8]) -> io::Result<Vec<u8>> {
+    if record.len() < HEADER_LEN || record[..4] != MAGIC {
+        // Legacy raw capture (or too short to carry a header): pass through.
+        return Ok(record.to_vec());
+    }
+    let version = record[4];
+    if version != FORMAT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported .dsql record version {version}"),
+        ));
+    }
+    let codec = FrameCodec::from_id(record[5]).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("unknown codec id {}", record[5]))
+    })?;
+    let prepass = record[6] != 0;
+    let payload = &record[HEADER_LEN..];
+
+    let staged = match codec {
+        FrameCodec::None => payload.to_vec(),
+        FrameCodec::Zstd => zstd::bulk::decompress(payload, max_decompressed(payload.len()))?,
+        FrameCodec::Lz4 => lz4_flex::block::decompress_size_prepended(payload)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
+    };
+
+    if prepass {
+        prepass::decode(&staged)
+    } else {
+        Ok(staged)
+    }
+}
+
+/// Generous upper bound for zstd bulk decompression of a stored frame.
+fn max_decompressed(compressed_len: usize) -> usize {
+    (compressed_len * 64).max(1024 * 1024)
+}
+
+/// Domain-specific pre-pass that collapses the stream's two most redundant
+/// patterns before the generic block codec sees it.
+///
+/// The stream is walked a 12-byte HWORD at a time and rewritten as a sequence of
+/// opcode-tagged blocks:
+///
+/// - `0x01` literal: a varint word count followed by that many words verbatim
+///   (pixel words, which carry independent coordinates).
+/// - `0x02` idle run: one idle word template plus a varint repeat count.
+/// - `0x03` header run: the run's first word, a varint word count, then each
+///   following word XORed against its predecessor so near-identical registers
+///   shrink to mostly-zero deltas.
+/// - `0x04` tail: a varint length and that many trailing bytes that do not form
+///   a whole HWORD (e.g. a SHA-512 integrity trailer).
+pub(crate) mod prepass {
+    use super::*;
+
+    const OP_LITERAL: u8 = 0x01;
+    const OP_IDLE_RUN: u8 = 0x02;
+    const OP_HEADER_RUN: u8 = 0x03;
+    const OP_TAIL: u8 = 0x04;
+
+    const HWORD: usize = 12;
+
+    /// Classify a 12-byte word by its control bits without a full parse.
+    fn control_bits(word: &[u8]) -> Option<ControlBits> {
+        // Control bits are the top 3 bits of the big-endian 96-bit word.
+        ControlBits::from_u8(word[0] >> 5)
+    }
+
+    fn push_varint(value: usize, out: &mut Vec<u8>) {
+        encode_unsigned(value as u128, out);
+    }
+
+    fn take_varint(bytes: &[u8], pos: &mut usize) -> io::Result<usize> {
+        let (value, consumed) = decode_unsigned(&bytes[*pos..])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        *pos += consumed;
+        Ok(value as usize)
+    }
+
+    /// Rewrite `frame` into the pre-pass block stream.
+    pub(crate) fn encode(frame: &[u8]) -> Vec<u8> {
+        let words = frame.len() / HWORD;
+        let mut out = Vec::with_capacity(frame.len());
+        let mut i = 0;
+
+        while i < words {
+            let word = &frame[i * HWORD..(i + 1) * HWORD];
+            match control_bits(word) {
+                Some(ControlBits::Idle) => {
+                    // Collapse a run of byte-identical idle words.
+                    let mut run = 1;
+                    while i + run < words && frame[(i + run) * HWORD..(i + run + 1) * HWORD] == *word {
+                        run += 1;
+                    }
+                    out.push(OP_IDLE_RUN);
+                    out.extend_from_slice(word);
+                    push_varint(run, &mut out);
+                    i += run;
+                }
+                Some(c) if c.is_header() => {
+                    // Delta-encode a run of consecutive header words.
+                    let mut run = 1;
+                    while i + run < words
+                        && control_bits(&frame[(i + run) * HWORD..(i + run + 1) * HWORD])
+                            .map(ControlBits::is_header)
+                            .unwrap_or(false)
+                    {
+                        run += 1;
+                    }
+                    out.push(OP_HEADER_RUN);
+                    out.extend_from_slice(word);
+                    push_varint(run, &mut out);
+                    for k in 1..run {
+                        let cur = &frame[(i + k) * HWORD..(i + k + 1) * HWORD];
+                        let prev = &frame[(i + k - 1) * HWORD..(i + k) * HWORD];
+                        for b in 0..HWORD {
+                            out.push(cur[b] ^ prev[b]);
+                        }
+                    }
+                    i += run;
+                }
+                _ => {
+                    // Literal run of everything that is neither idle nor header.
+                    let start = i;
+                    let mut run = 1;
+                    while i + run < words {
+                        match control_bits(&frame[(i + run) * HWORD..(i + run + 1) * HWORD]) {
+                            Some(c) if c.is_idle() || c.is_header() => break,
+                            _ => run += 1,
+                        }
+                    }
+                    out.push(OP_LITERAL);
+                    push_varint(run, &mut out);
+                    out.extend_from_slice(&frame[start * HWORD..(start + run) * HWORD]);
+                    i += run;
+                }
+            }
+        }
+
+        // Preserve any trailing bytes that do not form a whole HWORD.
+        let tail = &frame[words * HWORD..];
+        if !tail.is_empty() {
+            out.push(OP_TAIL);
+            push_varint(tail.len(), &mut out);
+            out.extend_from_slice(tail);
+        }
+
+        out
+    }
+
+    /// Reconstruct the original frame from a pre-pass block stream.
+    pub(crate) fn decode(stream: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(stream.len());
+        let mut pos = 0;
+
+        let word_at = |bytes: &[u8], at: usize| -> io::Result<[u8; HWORD]> {
+            bytes
+                .get(at..at + HWORD)
+                .and_then(|w| w.try_into().ok())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated pre-pass word"))
+        };
+
+        while pos < stream.len() {
+            let op = stream[pos];
+            pos += 1;
+            match op {
+                OP_LITERAL => {
+                    let run = take_varint(stream, &mut pos)?;
+                    let end = pos + run * HWORD;
+                    let bytes = stream
+                        .get(pos..end)
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated literal run"))?;
+                    out.extend_from_slice(bytes);
+                    pos = end;
+                }
+                OP_IDLE_RUN => {
+                    let word = word_at(stream, pos)?;
+                    pos += HWORD;
+                    let run = take_varint(stream, &mut pos)?;
+                    for _ in 0..run {
+                        out.extend_from_slice(&word);
+                    }
+                }
+                OP_HEADER_RUN => {
+                    let mut prev = word_at(stream, pos)?;
+                    pos += HWORD;
+                    let run = take_varint(stream, &mut pos)?;
+                    out.extend_from_slice(&prev);
+                    for _ in 1..run {
+                        let delta = word_at(stream, pos)?;
+                        pos += HWORD;
+                        for b in 0..HWORD {
+                            prev[b] ^= delta[b];
+                        }
+                        out.extend_from_slice(&prev);
+                    }
+                }
+                OP_TAIL => {
+                    let len = take_varint(stream, &mut pos)?;
+                    let end = pos + len;
+                    let bytes = stream
+                        .get(pos..end)
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated tail"))?;
+                    out.extend_from_slice(bytes);
+                    pos = end;
+                }
+                other => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown pre-pass opcode {other:#x}"),
+                    ))
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::hword::Endianness;
+
+    /// Build a 12-byte word with `control` in its top 3 bits and `data` below.
+    fn word(control: u8, data: u128) -> [u8; 12] {
+        Endianness::Big.write_word96(((control as u128) << 93) | (data & ((1u128 << 92) - 1)))
+    }
+
+    fn sample_frame() -> Vec<u8> {
+        let mut frame = Vec::new();
+        // Two header words with small register deltas.
+        frame.extend_from_slice(&word(0b010, 0x1000));
+        frame.extend_from_slice(&word(0b011, 0x1001));
+        // A few pixel words.
+        frame.extend_from_slice(&word(0b100, 0xABCD));
+        frame.extend_from_slice(&word(0b101, 0xABCE));
+        // A run of identical idle words.
+        for _ in 0..5 {
+            frame.extend_from_slice(&word(0b111, 0));
+        }
+        frame
+    }
+
+    #[test]
+    fn test_prepass_roundtrips() {
+        let frame = sample_frame();
+        let encoded = prepass::encode(&frame);
+        assert!(encoded.len() < frame.len()); // idle run collapsed
+        assert_eq!(prepass::decode(&encoded).unwrap(), frame);
+    }
+
+    #[test]
+    fn test_codec_roundtrip_all_variants() {
+        let frame = sample_frame();
+        for codec in [FrameCodec::None, FrameCodec::Zstd, FrameCodec::Lz4] {
+            for prepass in [false, true] {
+                let record = encode(codec, prepass, &frame).unwrap();
+                assert_eq!(decode(&record).unwrap(), frame, "{codec:?} prepass={prepass}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_raw_frame_passes_through() {
+        let frame = sample_frame();
+        // A raw capture with no DSQL header decodes to itself.
+        assert_eq!(decode(&frame).unwrap(), frame);
+    }
+}
@@ -0,0 +1,217 @@
+/*!
+Optional zstd compression of stored `.dsql` frames.
+
+The `.dsql` writer normally persists raw HWORD buffers, but header HWORDs are
+highly repetitive (110 per frame, each carrying small-delta registers), so they
+compress extremely well once zstd has a dictionary trained on representative
+frames. [`FrameCompressor`] buffers the first `train_after_frames` completed
+frames, trains a dictionary over them, persists it, and then reuses that one
+dictionary to compress every later frame — the dictionary-reuse split zstd's
+bulk API is built around.
+
+Each compressed record is laid out as:
+
+```text
+[dict_id u32 LE][compressed_len u32 LE][zstd frame]
+```
+
+A `dict_id` of `0` marks a frame compressed without a dictionary (the frames
+captured while the dictionary was still training). [`FrameDecompressor`] reads
+the id back, selects the matching dictionary, and reinitializes a bulk decoder
+per frame so batch re-reads stay cheap.
+*/
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::config::CompressionConfig;
+
+/// Target size of a trained dictionary, in bytes.
+const DICTIONARY_CAPACITY: usize = 64 * 1024;
+
+/// zstd dictionary magic (little-endian) that prefixes a trained dictionary.
+const DICT_MAGIC: u32 = 0xEC30_A437;
+
+/// Bytes prepended to every compressed record: `dict_id` + `compressed_len`.
+const RECORD_HEADER_LEN: usize = 8;
+
+/// Read the dictionary id embedded in a trained zstd dictionary.
+///
+/// Trained dictionaries begin with the magic number followed by a little-endian
+/// `u32` id. A raw dictionary (one that omits the header) has no id, so this
+/// returns `0`, matching the "no dictionary" sentinel.
+fn dictionary_id(dict: &[u8]) -> u32 {
+    if dict.len() < RECORD_HEADER_LEN {
+        return 0;
+    }
+    let magic = u32::from_le_bytes([dict[0], dict[1], dict[2], dict[3]]);
+    if magic != DICT_MAGIC {
+        return 0;
+    }
+    u32::from_le_bytes([dict[4], dict[5], dict[6], dict[7]])
+}
+
+/// Frame serializer that optionally compresses frames with a trained dictionary.
+pub struct FrameCompressor {
+    level: i32,
+    dictionary_path: Option<PathBuf>,
+    train_after_frames: usize,
+    /// Frames buffered while the dictionary is still being trained.
+    samples: Vec<Vec<u8>>,
+    /// The active dictionary, once loaded or trained.
+    dictionary: Option<Vec<u8>>,
+    /// Id of the active dictionary (`0` until one is ready).
+    dict_id: u32,
+    /// Reused bulk compressor bound to the active dictionary.
+    compressor: zstd::bulk::Compressor<'static>,
+}
+
+impl FrameCompressor {
+    /// Build a compressor from the application compression settings.
+    ///
+    /// If `dictionary_path` already points at a dictionary on disk it is loaded
+    /// immediately and training is skipped; otherwise the first
+    /// `train_after_frames` frames are buffered to train one.
+    pub fn new(config: &CompressionConfig) -> io::Result<Self> {
+        let dictionary_path = config.dictionary_path.as_ref().map(PathBuf::from);
+
+        let mut compressor = Self {
+            level: config.level,
+            dictionary_path,
+            train_after_frames: config.train_after_frames,
+            samples: Vec::new(),
+            dictionary: None,
+            dict_id: 0,
+            compressor: zstd::bulk::Compressor::new(config.level)?,
+        };
+
+        if let Some(path) = compressor.dictionary_path.as_ref() {
+            if path.is_file() {
+                let dict = std::fs::read(path)?;
+                info!("📚 Loaded zstd dictionary from {} ({} bytes)", path.display(), dict.len());
+                compressor.install_dictionary(dict)?;
+            }
+        }
+
+        Ok(compressor)
+    }
+
+    /// Serialize one completed frame into a compressed `.dsql` record.
+    ///
+    /// While the dictionary is training the frame is buffered (and compressed
+    /// without a dictionary so the on-disk record format stays uniform). Once
+    /// enough samples have accumulated the dictionary is trained, persisted, and
+    /// reused for this and every subsequent frame.
+    pub fn compress_frame(&mut self, frame: &[u8]) -> io::Result<Vec<u8>> {
+        if self.dictionary.is_none() && self.train_after_frames > 0 {
+            self.samples.push(frame.to_vec());
+            if self.samples.len() >= self.train_after_frames {
+                self.train_dictionary()?;
+            }
+        }
+
+        let compressed = self.compressor.compress(frame)?;
+        Ok(Self::frame_record(self.dict_id, &compressed))
+    }
+
+    /// Train a dictionary over the buffered samples, persist it, and switch the
+    /// compressor over to it.
+    fn train_dictionary(&mut self) -> io::Result<()> {
+        let dict = zstd::dict::from_samples(&self.samples, DICTIONARY_CAPACITY)?;
+        info!("📚 Trained zstd dictionary over {} frames ({} bytes)",
+              self.samples.len(), dict.len());
+
+        if let Some(path) = self.dictionary_path.as_ref() {
+            std::fs::write(path, &dict)?;
+            info!("📚 Persisted dictionary to {}", path.display());
+        } else {
+            warn!("⚠️ No dictionary_path set; trained dictionary will not persist across runs");
+        }
+
+        self.samples.clear();
+        self.install_dictionary(dict)
+    }
+
+    /// Bind the reused compressor to `dict` and record its id.
+    fn install_dictionary(&mut self, dict: Vec<u8>) -> io::Result<()> {
+        self.compressor = zstd::bulk::Compressor::with_dictionary(self.level, &dict)?;
+        self.dict_id = dictionary_id(&dict);
+        self.dictionary = Some(dict);
+        Ok(())
+    }
+
+    /// Assemble the `[dict_id][compressed_len][zstd frame]` record.
+    fn frame_record(dict_id: u32, compressed: &[u8]) -> Vec<u8> {
+        let mut record = Vec::with_capacity(RECORD_HEADER_LEN + compressed.len());
+        record.extend_from_slice(&dict_id.to_le_bytes());
+        record.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        record.extend_from_slice(compressed);
+        record
+    }
+}
+
+/// Reader counterpart to [`FrameCompressor`] for batch re-reads.
+///
+/// Dictionaries are registered once by id; each record then reinitializes a
+/// bulk decoder from the registered dictionary bytes rather than cloning a
+/// prepared decoder object, keeping repeated reads cheap.
+#[derive(Default)]
+pub struct FrameDecompressor {
+    dictionaries: HashMap<u32, Vec<u8>>,
+}
+
+impl FrameDecompressor {
+    /// Create an empty decompressor with no dictionaries registered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a dictionary so records tagged with its id can be decoded.
+    pub fn register_dictionary(&mut self, dict: Vec<u8>) {
+        let id = dictionary_id(&dict);
+        self.dictionaries.insert(id, dict);
+    }
+
+    /// Decode one `[dict_id][compressed_len][zstd frame]` record back to the raw
+    /// HWORD buffer.
+    pub fn decompress_record(&self, record: &[u8]) -> io::Result<Vec<u8>> {
+        if record.len() < RECORD_HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "record shorter than header",
+            ));
+        }
+
+        let dict_id = u32::from_le_bytes([record[0], record[1], record[2], record[3]]);
+        let compressed_len =
+            u32::from_le_bytes([record[4], record[5], record[6], record[7]]) as usize;
+        let payload = &record[RECORD_HEADER_LEN..];
+        if payload.len() < compressed_len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "record truncated before compressed_len",
+            ));
+        }
+        let frame = &payload[..compressed_len];
+
+        // Reinitialize a bulk decoder per frame from the registered dictionary.
+        let mut decoder = if dict_id == 0 {
+            zstd::bulk::Decompressor::new()?
+        } else {
+            let dict = self.dictionaries.get(&dict_id).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no dictionary registered for id {dict_id}"),
+                )
+            })?;
+            zstd::bulk::Decompressor::with_dictionary(dict)?
+        };
+
+        // zstd bulk decompression needs an upper bound; frames are small relative
+        // to the configured max frame size, so a generous cap is safe.
+        decoder.decompress(frame, DICTIONARY_CAPACITY.max(frame.len() * 64))
+    }
+}
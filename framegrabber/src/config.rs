@@ -77,9 +77,189 @@ pub struct FrameGrabberConfig {
     
     /// Maximum frame size in HWORDs
     pub max_frame_size_hwords: usize,
-    
+
+    /// Per-frame inactivity timeout in milliseconds.
+    ///
+    /// If a partially assembled frame stops advancing for longer than this, the
+    /// sync-engine watchdog aborts it and flushes whatever was collected as a
+    /// degraded frame rather than growing `frame_buffer` unbounded.
+    #[serde(default = "default_frame_timeout_ms")]
+    pub frame_timeout_ms: u64,
+
     /// Statistics reporting interval in seconds
     pub stats_interval_seconds: u64,
+
+    /// Core index to pin the UDP receiver / tokio runtime thread to.
+    ///
+    /// Pinning the receiver and writer to separate isolated cores keeps the
+    /// writer from being descheduled behind the receiver under load. `None`
+    /// (the default) leaves the thread unpinned.
+    #[serde(default)]
+    pub receiver_core: Option<usize>,
+
+    /// Core index to pin the file-writer thread to. `None` leaves it unpinned.
+    #[serde(default)]
+    pub writer_core: Option<usize>,
+
+    /// Target output frame rate. Whole frames are dropped to hit it, keeping the
+    /// most recent. `None` (the default) emits every completed frame.
+    #[serde(default)]
+    pub output_fps: Option<f64>,
+
+    /// Point decimation factor for decode-mode output (`1` = every point,
+    /// `n` = every nth point).
+    #[serde(default = "default_output_decimation")]
+    pub output_decimation: usize,
+
+    /// Coordinate transform pipeline applied to decoded points before they are
+    /// emitted or saved. Applied in order; empty (the default) leaves decoded
+    /// coordinates untouched. See [`shared::TransformSpec`].
+    #[serde(default)]
+    pub transforms: Vec<shared::TransformSpec>,
+
+    /// Header register map for the connected instrument firmware revision.
+    ///
+    /// Lets a different firmware revision be supported by editing the config
+    /// rather than recompiling. Defaults to the reference layout.
+    #[serde(default)]
+    pub header_layout: shared::HeaderLayout,
+
+    /// Optional zstd compression of stored `.dsql` frames.
+    #[serde(default)]
+    pub compression: CompressionConfig,
+
+    /// Block codec applied to each stored `.dsql` frame (`none`, `zstd`, `lz4`).
+    ///
+    /// Independent of the dictionary-trained [`compression`](Self::compression)
+    /// path: when a codec is selected each frame is wrapped in a self-describing
+    /// record so downstream tools can pick the right decoder.
+    #[serde(default)]
+    pub frame_codec: crate::codec::FrameCodec,
+
+    /// Run the idle/header pre-pass before the `frame_codec` block codec.
+    #[serde(default)]
+    pub codec_prepass: bool,
+
+    /// Emit decoded coordinates instead of raw HWORD data in live output.
+    #[serde(default)]
+    pub decode: bool,
+
+    /// Generate synthetic LiDAR data instead of binding a UDP socket.
+    #[serde(default)]
+    pub debug: bool,
+
+    /// Optional `host:port` for the runtime config control channel. When set, a
+    /// TCP control server is started alongside capture so the configuration can
+    /// be read, written, and erased without a restart (see
+    /// [`config_store`](crate::config_store)).
+    #[serde(default)]
+    pub control_addr: Option<String>,
+
+    /// How the imaging-mode pixel count is decoded from the header registers.
+    ///
+    /// A single 16-bit register tops out at 65,535, but imaging mode can reach
+    /// ~122,000 pixels, so some firmware splits the count across two registers
+    /// or scales a single one. Selecting the layout here supports those
+    /// revisions without a code change.
+    #[serde(default)]
+    pub pixel_count_layout: PixelCountLayout,
+}
+
+/// Strategy for reconstructing the imaging-mode pixel count from header
+/// registers. Each register is a 16-bit field; `reg` indices count registers
+/// from the start of the first header HWORD.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PixelCountLayout {
+    /// A single 16-bit register holds the count (max 65,535).
+    Single16 { reg: usize },
+    /// The count is split across two registers: `high_reg` holds the high 16
+    /// bits and `low_reg` the low 16 bits of a `u32`.
+    Split16x16 { high_reg: usize, low_reg: usize },
+    /// A single register holds the count divided by `factor` (reconstructed by
+    /// multiplying back).
+    Scaled { reg: usize, factor: u32 },
+}
+
+impl Default for PixelCountLayout {
+    fn default() -> Self {
+        // The reference layout reads NUM_PIXELS_RW from Register 2.
+        PixelCountLayout::Single16 { reg: 2 }
+    }
+}
+
+impl std::str::FromStr for PixelCountLayout {
+    type Err = String;
+
+    /// Parse the compact CLI form used by the `capture` subcommand:
+    /// `single16:<reg>`, `split16x16:<high>,<low>`, or `scaled:<reg>,<factor>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, rest) = s.split_once(':').unwrap_or((s, ""));
+        let parse = |v: &str| -> Result<u64, String> {
+            v.trim()
+                .parse::<u64>()
+                .map_err(|_| format!("invalid register/factor '{v}'"))
+        };
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "single16" => Ok(PixelCountLayout::Single16 { reg: parse(rest)? as usize }),
+            "split16x16" => {
+                let (high, low) = rest
+                    .split_once(',')
+                    .ok_or_else(|| "split16x16 expects '<high_reg>,<low_reg>'".to_string())?;
+                Ok(PixelCountLayout::Split16x16 {
+                    high_reg: parse(high)? as usize,
+                    low_reg: parse(low)? as usize,
+                })
+            }
+            "scaled" => {
+                let (reg, factor) = rest
+                    .split_once(',')
+                    .ok_or_else(|| "scaled expects '<reg>,<factor>'".to_string())?;
+                Ok(PixelCountLayout::Scaled {
+                    reg: parse(reg)? as usize,
+                    factor: parse(factor)? as u32,
+                })
+            }
+            other => Err(format!(
+                "unknown pixel count layout '{other}' (expected single16, split16x16, or scaled)"
+            )),
+        }
+    }
+}
+
+/// zstd compression settings for stored frames.
+///
+/// Header HWORDs are highly repetitive (110 per frame with small-delta
+/// registers), so a dictionary trained on a handful of captured frames makes
+/// each subsequent frame compress well. When `enabled`, the first
+/// `train_after_frames` frames are buffered to train a dictionary, which is
+/// persisted to `dictionary_path` and then reused to compress every later frame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Compress stored frames with zstd.
+    pub enabled: bool,
+
+    /// zstd compression level (1..=22).
+    pub level: i32,
+
+    /// Where the trained dictionary is persisted / loaded from.
+    pub dictionary_path: Option<String>,
+
+    /// Number of frames to buffer for dictionary training before compression
+    /// switches to the trained dictionary. `0` disables training (frames are
+    /// compressed without a dictionary).
+    pub train_after_frames: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            level: 3,
+            dictionary_path: None,
+            train_after_frames: 64,
+        }
+    }
 }
 
 impl Default for FrameGrabberConfig {
@@ -93,11 +273,35 @@ impl Default for FrameGrabberConfig {
             enable_realtime_processing: false,
             drop_parity_errors: false,
             max_frame_size_hwords: 1000000, // 1M HWORDs max per frame
+            frame_timeout_ms: default_frame_timeout_ms(),
             stats_interval_seconds: 10,
+            receiver_core: None,
+            writer_core: None,
+            output_fps: None,
+            output_decimation: default_output_decimation(),
+            transforms: Vec::new(),
+            header_layout: shared::HeaderLayout::default(),
+            compression: CompressionConfig::default(),
+            frame_codec: crate::codec::FrameCodec::default(),
+            codec_prepass: false,
+            decode: false,
+            debug: false,
+            control_addr: None,
+            pixel_count_layout: PixelCountLayout::default(),
         }
     }
 }
 
+/// Default per-frame inactivity timeout (5 seconds).
+fn default_frame_timeout_ms() -> u64 {
+    5000
+}
+
+/// Default point decimation factor (`1` = keep every point).
+fn default_output_decimation() -> usize {
+    1
+}
+
 /// GUI specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GuiConfig {
@@ -0,0 +1,337 @@
+/*!
+Network-accessible runtime key-value configuration store.
+
+Configuration historically flowed one way: [`AppConfig::load_from_file`] read a
+TOML at startup and that was that — a long-running headless `framegrabber` could
+not be retuned without a restart. [`ConfigStore`] models the key=value store and
+read/write/remove semantics of an instrument control manager on top of the
+existing [`AppConfig`]: it exposes a fixed set of typed [`ConfigKey`]s that can
+be queried and mutated over a small line-oriented control channel (TCP or a Unix
+socket), and every mutation is persisted straight back to the backing TOML so
+the change survives a restart. A capture that is already running does not read
+the store: it keeps the settings it started with, and a change takes effect the
+next time capture starts.
+
+The wire protocol is one request per line:
+
+```text
+GET <key>          -> OK <value>    | ERR <reason>
+SET <key> <value>  -> OK            | ERR <reason>
+ERASE <key>        -> OK            | ERR <reason>   (resets the key to default)
+LIST               -> OK k1=v1 k2=v2 ...
+```
+
+Each key maps onto a field of [`FrameGrabberConfig`](crate::config::FrameGrabberConfig);
+`ERASE` restores that field to its default, mirroring how removing a key from an
+instrument config reverts it to the firmware default.
+*/
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::{error, info, warn};
+
+use crate::codec::FrameCodec;
+use crate::config::{AppConfig, FrameGrabberConfig};
+
+/// The typed keys the control channel can read, write, and erase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    BindAddr,
+    Port,
+    OutputDirectory,
+    EnableStorage,
+    Decode,
+    Debug,
+    Compress,
+}
+
+impl ConfigKey {
+    /// Every key, in listing order.
+    pub const ALL: [ConfigKey; 7] = [
+        ConfigKey::BindAddr,
+        ConfigKey::Port,
+        ConfigKey::OutputDirectory,
+        ConfigKey::EnableStorage,
+        ConfigKey::Decode,
+        ConfigKey::Debug,
+        ConfigKey::Compress,
+    ];
+
+    /// The wire name of this key.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfigKey::BindAddr => "bind_addr",
+            ConfigKey::Port => "port",
+            ConfigKey::OutputDirectory => "output_directory",
+            ConfigKey::EnableStorage => "enable_storage",
+            ConfigKey::Decode => "decode",
+            ConfigKey::Debug => "debug",
+            ConfigKey::Compress => "compress",
+        }
+    }
+
+    fn from_str(name: &str) -> Option<Self> {
+        ConfigKey::ALL.into_iter().find(|k| k.as_str() == name)
+    }
+
+    /// Read the current value of this key as a string.
+    fn get(self, fg: &FrameGrabberConfig) -> String {
+        match self {
+            ConfigKey::BindAddr => fg.udp_bind_addr.clone(),
+            ConfigKey::Port => fg.udp_port.to_string(),
+            ConfigKey::OutputDirectory => fg.output_directory.clone(),
+            ConfigKey::EnableStorage => fg.enable_storage.to_string(),
+            ConfigKey::Decode => fg.decode.to_string(),
+            ConfigKey::Debug => fg.debug.to_string(),
+            ConfigKey::Compress => match fg.frame_codec {
+                FrameCodec::None => "none".to_string(),
+                FrameCodec::Zstd => "zstd".to_string(),
+                FrameCodec::Lz4 => "lz4".to_string(),
+            },
+        }
+    }
+
+    /// Parse and apply `value` to this key, or describe why it is invalid.
+    fn set(self, fg: &mut FrameGrabberConfig, value: &str) -> Result<(), String> {
+        match self {
+            ConfigKey::BindAddr => fg.udp_bind_addr = value.to_string(),
+            ConfigKey::Port => {
+                fg.udp_port = value.parse().map_err(|_| format!("invalid port '{value}'"))?
+            }
+            ConfigKey::OutputDirectory => fg.output_directory = value.to_string(),
+            ConfigKey::EnableStorage => fg.enable_storage = parse_bool(value)?,
+            ConfigKey::Decode => fg.decode = parse_bool(value)?,
+            ConfigKey::Debug => fg.debug = parse_bool(value)?,
+            ConfigKey::Compress => fg.frame_codec = value.parse()?,
+        }
+        Ok(())
+    }
+
+    /// Reset this key to the [`FrameGrabberConfig`] default.
+    fn reset(self, fg: &mut FrameGrabberConfig) {
+        let default = FrameGrabberConfig::default();
+        match self {
+            ConfigKey::BindAddr => fg.udp_bind_addr = default.udp_bind_addr,
+            ConfigKey::Port => fg.udp_port = default.udp_port,
+            ConfigKey::OutputDirectory => fg.output_directory = default.output_directory,
+            ConfigKey::EnableStorage => fg.enable_storage = default.enable_storage,
+            ConfigKey::Decode => fg.decode = default.decode,
+            ConfigKey::Debug => fg.debug = default.debug,
+            ConfigKey::Compress => fg.frame_codec = default.frame_codec,
+        }
+    }
+}
+
+/// Parse a boolean from the common textual spellings.
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "on" | "yes" => Ok(true),
+        "false" | "0" | "off" | "no" => Ok(false),
+        other => Err(format!("invalid bool '{other}'")),
+    }
+}
+
+/// Shared, persisted view of the application configuration.
+///
+/// Cloning shares the same underlying config and backing file, so the control
+/// server and the rest of the application observe each other's writes.
+#[derive(Clone)]
+pub struct ConfigStore {
+    config: Arc<Mutex<AppConfig>>,
+    path: PathBuf,
+}
+
+impl ConfigStore {
+    /// Wrap `config`, persisting mutations back to `path`.
+    pub fn new(config: AppConfig, path: PathBuf) -> Self {
+        Self { config: Arc::new(Mutex::new(config)), path }
+    }
+
+    /// Snapshot the current configuration.
+    pub fn snapshot(&self) -> AppConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Handle one protocol line, returning the response line (without newline).
+    pub fn handle_line(&self, line: &str) -> String {
+        let mut tokens = line.split_whitespace();
+        let Some(command) = tokens.next() else {
+            return "ERR empty request".to_string();
+        };
+        match command.to_ascii_uppercase().as_str() {
+            "GET" => match tokens.next().and_then(ConfigKey::from_str) {
+                Some(key) => format!("OK {}", key.get(&self.config.lock().unwrap().framegrabber)),
+                None => "ERR unknown key".to_string(),
+            },
+            "SET" => {
+                let Some(key) = tokens.next().and_then(ConfigKey::from_str) else {
+                    return "ERR unknown key".to_string();
+                };
+                let value = tokens.collect::<Vec<_>>().join(" ");
+                self.mutate(|fg| key.set(fg, &value))
+            }
+            "ERASE" => match tokens.next().and_then(ConfigKey::from_str) {
+                Some(key) => self.mutate(|fg| {
+                    key.reset(fg);
+                    Ok(())
+                }),
+                None => "ERR unknown key".to_string(),
+            },
+            "LIST" => {
+                let fg = &self.config.lock().unwrap().framegrabber;
+                let pairs: Vec<String> =
+                    ConfigKey::ALL.iter().map(|k| format!("{}={}", k.as_str(), k.get(fg))).collect();
+                format!("OK {}", pairs.join(" "))
+            }
+            other => format!("ERR unknown command '{other}'"),
+        }
+    }
+
+    /// Apply `f` to the framegrabber config, persist on success, and format the
+    /// response line.
+    fn mutate(&self, f: impl FnOnce(&mut FrameGrabberConfig) -> Result<(), String>) -> String {
+        let mut config = self.config.lock().unwrap();
+        if let Err(e) = f(&mut config.framegrabber) {
+            return format!("ERR {e}");
+        }
+        match config.save_to_file(&self.path) {
+            Ok(()) => "OK".to_string(),
+            Err(e) => format!("ERR failed to persist: {e}"),
+        }
+    }
+
+    /// Serve the control protocol on a TCP address until `running` is cleared.
+    pub fn serve_tcp(&self, addr: &str, running: Arc<AtomicBool>) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        info!("🎛️  Config control channel listening on tcp://{addr}");
+        self.accept_loop(|| match listener.accept() {
+            Ok((stream, _)) => Ok(Some(Box::new(stream))),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }, running)
+    }
+
+    /// Serve the control protocol on a Unix socket until `running` is cleared.
+    pub fn serve_unix(&self, path: &str, running: Arc<AtomicBool>) -> std::io::Result<()> {
+        let _ = std::fs::remove_file(path); // clear a stale socket from a prior run
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        info!("🎛️  Config control channel listening on unix:{path}");
+        self.accept_loop(|| match listener.accept() {
+            Ok((stream, _)) => Ok(Some(Box::new(stream))),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }, running)
+    }
+
+    /// Shared accept/serve loop for both transports. `accept` yields the next
+    /// connection (or `None` when none is pending) so shutdown stays responsive.
+    fn accept_loop(
+        &self,
+        mut accept: impl FnMut() -> std::io::Result<Option<Box<dyn ReadWrite>>>,
+        running: Arc<AtomicBool>,
+    ) -> std::io::Result<()> {
+        while running.load(Ordering::SeqCst) {
+            match accept() {
+                Ok(Some(stream)) => {
+                    if let Err(e) = self.serve_connection(stream) {
+                        warn!("Config control connection error: {e}");
+                    }
+                }
+                Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+                Err(e) => {
+                    error!("Config control accept error: {e}");
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve one client connection line-by-line until it closes.
+    fn serve_connection(&self, stream: Box<dyn ReadWrite>) -> std::io::Result<()> {
+        let mut writer = stream.try_clone_box()?;
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(()); // client closed
+            }
+            let response = self.handle_line(line.trim_end());
+            writeln!(writer, "{response}")?;
+            writer.flush()?;
+        }
+    }
+}
+
+/// Byte stream that can be both read and written and handed a cloned writer.
+trait ReadWrite: std::io::Read + std::io::Write + Send {
+    fn try_clone_box(&self) -> std::io::Result<Box<dyn std::io::Write + Send>>;
+}
+
+impl ReadWrite for std::net::TcpStream {
+    fn try_clone_box(&self) -> std::io::Result<Box<dyn std::io::Write + Send>> {
+        Ok(Box::new(self.try_clone()?))
+    }
+}
+
+impl ReadWrite for std::os::unix::net::UnixStream {
+    fn try_clone_box(&self) -> std::io::Result<Box<dyn std::io::Write + Send>> {
+        Ok(Box::new(self.try_clone()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn store() -> (ConfigStore, NamedTempFile) {
+        let file = NamedTempFile::new().unwrap();
+        let store = ConfigStore::new(AppConfig::new(), file.path().to_path_buf());
+        (store, file)
+    }
+
+    #[test]
+    fn test_get_set_erase_roundtrip() {
+        let (store, _file) = store();
+
+        assert_eq!(store.handle_line("GET port"), "OK 12345");
+        assert_eq!(store.handle_line("SET port 6000"), "OK");
+        assert_eq!(store.handle_line("GET port"), "OK 6000");
+
+        // ERASE restores the default.
+        assert_eq!(store.handle_line("ERASE port"), "OK");
+        assert_eq!(store.handle_line("GET port"), "OK 12345");
+    }
+
+    #[test]
+    fn test_set_persists_to_toml() {
+        let (store, file) = store();
+        assert_eq!(store.handle_line("SET output_directory /tmp/caps"), "OK");
+        let reloaded = AppConfig::load_from_file(file.path()).unwrap();
+        assert_eq!(reloaded.framegrabber.output_directory, "/tmp/caps");
+    }
+
+    #[test]
+    fn test_invalid_requests_report_errors() {
+        let (store, _file) = store();
+        assert!(store.handle_line("GET nope").starts_with("ERR"));
+        assert!(store.handle_line("SET port notanumber").starts_with("ERR"));
+        assert!(store.handle_line("FROB port 1").starts_with("ERR"));
+    }
+
+    #[test]
+    fn test_compress_key_roundtrips_codec() {
+        let (store, _file) = store();
+        assert_eq!(store.handle_line("SET compress zstd"), "OK");
+        assert_eq!(store.handle_line("GET compress"), "OK zstd");
+    }
+}
@@ -6,16 +6,46 @@ This module implements the frame synchronization logic as described in the
 index validation instead of signature-based detection.
 */
 
+use std::time::{Duration, Instant};
+
 use tracing::{info, warn, debug};
 use shared::hword::HWord;
 use shared::protocol::HEADER_HWORDS_PER_FRAME;
 
+use crate::config::PixelCountLayout;
+
 /// Idle HWORD pattern for initial synchronization (control bits = 111)
 /// Pattern: 0xFD3C4B5A69788796A5B4C3B2 (12 bytes)
 const IDLE_HWORD_PATTERN: [u8; 12] = [
     0xFD, 0x3C, 0x4B, 0x5A, 0x69, 0x78, 0x87, 0x96, 0xA5, 0xB4, 0xC3, 0xB2
 ];
 
+/// Placeholder inserted in place of a header HWORD that was lost in transit.
+///
+/// The control bits are set to `010` (FirstHeader) so a downstream parser still
+/// walks it as a header slot, but every data/parity bit is zero so the synthetic
+/// origin is obvious. Each one inserted bumps [`FrameQuality::header_gaps`].
+const PLACEHOLDER_HEADER_HWORD: [u8; 12] = [
+    0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+];
+
+/// Default cap on how many consecutive header HWORDs may be reconstructed from
+/// a single index jump before the gap is treated as a loss of sync rather than
+/// a recoverable dropout.
+const DEFAULT_MAX_HEADER_GAP: usize = 8;
+
+/// Default number of consecutive degraded frames tolerated before the engine
+/// forces a resync and waits for a fresh Idle HWORD.
+const DEFAULT_MAX_CONSECUTIVE_DEGRADED: u32 = 3;
+
+/// Default per-frame inactivity timeout. A frame that stops advancing for longer
+/// than this is aborted by the watchdog and emitted as a degraded frame.
+const DEFAULT_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default cap on HWORDs per frame, mirroring
+/// [`FrameGrabberConfig::max_frame_size_hwords`](crate::config::FrameGrabberConfig).
+const DEFAULT_MAX_FRAME_SIZE_HWORDS: usize = 1_000_000;
+
 /// Frame synchronization state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameSyncState {
@@ -72,6 +102,156 @@ impl FrameMode {
     }
 }
 
+/// Quality report attached to every frame emitted by the sync engine.
+///
+/// A frame is `degraded` when it was salvaged rather than received cleanly:
+/// header HWORDs were reconstructed from an index gap (`header_gaps`), the
+/// frame was cut short by a new `FrameStart` before all pixels arrived
+/// (`pixel_shortfall`), or the watchdog aborted it for stalling or exceeding
+/// `max_frame_size_hwords`. Downstream consumers can use this to prefer clean
+/// keyframes or to trigger a resync request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameQuality {
+    /// Number of header HWORDs reconstructed from index gaps.
+    pub header_gaps: usize,
+    /// Number of pixel HWORDs missing relative to the detected mode.
+    pub pixel_shortfall: usize,
+    /// True when the frame was salvaged rather than received intact.
+    pub degraded: bool,
+}
+
+impl FrameQuality {
+    /// Build a quality report, marking it degraded if anything was salvaged.
+    fn new(header_gaps: usize, pixel_shortfall: usize) -> Self {
+        Self {
+            header_gaps,
+            pixel_shortfall,
+            degraded: header_gaps > 0 || pixel_shortfall > 0,
+        }
+    }
+}
+
+/// The 110-HWORD header block of a parsed frame, with decoded accessors.
+///
+/// Wraps the header HWORDs so downstream code reads registers through typed
+/// methods instead of repeating the bit-extraction in
+/// [`FrameSyncEngine`](crate::frame_sync::FrameSyncEngine).
+#[derive(Debug, Clone)]
+pub struct HeaderBlock {
+    hwords: Vec<HWord>,
+}
+
+impl HeaderBlock {
+    /// The raw header HWORDs in wire order.
+    pub fn hwords(&self) -> &[HWord] {
+        &self.hwords
+    }
+
+    /// Header index (bits 87:84) of the `i`-th header HWORD, if present.
+    pub fn header_index(&self, i: usize) -> Option<u8> {
+        self.hwords.get(i).and_then(FrameSyncEngine::extract_header_index)
+    }
+
+    /// Whether the first header HWORD carries the frame-start control bits.
+    pub fn is_frame_start(&self) -> bool {
+        self.hwords
+            .first()
+            .map(|h| h.control_bits.is_frame_start())
+            .unwrap_or(false)
+    }
+
+    /// Declared pixel count decoded from the first header HWORD (Register 2).
+    pub fn num_pixels(&self) -> Option<usize> {
+        self.hwords.first().and_then(FrameSyncEngine::extract_num_pixels)
+    }
+}
+
+/// The pixel block of a parsed frame.
+#[derive(Debug, Clone)]
+pub struct PixelBlock {
+    hwords: Vec<HWord>,
+}
+
+impl PixelBlock {
+    /// The raw pixel HWORDs in wire order.
+    pub fn hwords(&self) -> &[HWord] {
+        &self.hwords
+    }
+
+    /// Number of pixel HWORDs in the block.
+    pub fn len(&self) -> usize {
+        self.hwords.len()
+    }
+
+    /// Whether the block carries no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.hwords.is_empty()
+    }
+}
+
+/// A fully parsed frame: typed header/pixel blocks plus the quality report.
+///
+/// Consumers get typed field access without re-parsing the raw `Vec<u8>` that
+/// [`FrameSyncEngine::process_hword`](crate::frame_sync::FrameSyncEngine::process_hword)
+/// returns. [`from_bytes`](Self::from_bytes) and [`to_bytes`](Self::to_bytes)
+/// round-trip losslessly so a parsed frame can be re-serialized for storage.
+#[derive(Debug, Clone)]
+pub struct ParsedFrame {
+    /// Detected frame mode (scan/imaging).
+    pub mode: FrameMode,
+    /// The header block (110 HWORDs in a nominal frame).
+    pub header: HeaderBlock,
+    /// The pixel block.
+    pub pixels: PixelBlock,
+    /// Quality report carried over from the sync engine.
+    pub quality: FrameQuality,
+}
+
+impl ParsedFrame {
+    /// Parse a raw frame buffer into typed header and pixel blocks.
+    ///
+    /// HWORDs are classified by their control bits: leading header HWORDs are
+    /// collected until the first pixel HWORD, after which the remainder are
+    /// treated as pixels. The resulting [`quality`](Self::quality) is
+    /// [`FrameQuality::default`] — callers that know the salvage history (the
+    /// sync engine) set it explicitly.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if bytes.len() % 12 != 0 {
+            return Err(format!("frame length {} is not a multiple of 12", bytes.len()).into());
+        }
+
+        let mut header = Vec::new();
+        let mut pixels = Vec::new();
+        for chunk in bytes.chunks_exact(12) {
+            let word: [u8; 12] = chunk.try_into().expect("chunk is 12 bytes");
+            let hword = HWord::from_bytes(&word)?;
+            if pixels.is_empty() && !hword.control_bits.is_pixel() {
+                header.push(hword);
+            } else {
+                pixels.push(hword);
+            }
+        }
+
+        let mode = FrameMode::detect(header.len(), pixels.len());
+        Ok(Self {
+            mode,
+            header: HeaderBlock { hwords: header },
+            pixels: PixelBlock { hwords: pixels },
+            quality: FrameQuality::default(),
+        })
+    }
+
+    /// Re-serialize the frame to its raw HWORD buffer.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let total = self.header.hwords.len() + self.pixels.hwords.len();
+        let mut bytes = Vec::with_capacity(total * 12);
+        for hword in self.header.hwords.iter().chain(self.pixels.hwords.iter()) {
+            bytes.extend_from_slice(&hword.to_bytes());
+        }
+        bytes
+    }
+}
+
 /// Frame synchronization engine
 pub struct FrameSyncEngine {
     state: FrameSyncState,
@@ -80,6 +260,26 @@ pub struct FrameSyncEngine {
     frames_completed: u64,
     sync_errors: u64,
     header_index_errors: u64,
+    /// Header gaps salvaged within the frame currently being collected.
+    current_header_gaps: usize,
+    /// Consecutive degraded frames emitted without a clean one in between.
+    consecutive_degraded: u32,
+    /// Largest index gap that may be reconstructed instead of dropping sync.
+    max_header_gap: usize,
+    /// Degraded-frame run length that forces a resync.
+    max_consecutive_degraded: u32,
+    /// Maximum HWORDs a single frame may accumulate before the watchdog aborts it.
+    max_frame_size_hwords: usize,
+    /// Inactivity window after which an in-progress frame is aborted.
+    frame_timeout: Duration,
+    /// Instant the in-progress frame last advanced, `None` when idle.
+    last_activity: Option<Instant>,
+    /// Frames aborted because they stalled past `frame_timeout`.
+    frame_timeouts: u64,
+    /// Frames aborted because they exceeded `max_frame_size_hwords`.
+    oversize_aborts: u64,
+    /// How the imaging-mode pixel count is decoded from header registers.
+    pixel_count_layout: PixelCountLayout,
 }
 
 impl FrameSyncEngine {
@@ -92,6 +292,33 @@ impl FrameSyncEngine {
             frames_completed: 0,
             sync_errors: 0,
             header_index_errors: 0,
+            current_header_gaps: 0,
+            consecutive_degraded: 0,
+            max_header_gap: DEFAULT_MAX_HEADER_GAP,
+            max_consecutive_degraded: DEFAULT_MAX_CONSECUTIVE_DEGRADED,
+            max_frame_size_hwords: DEFAULT_MAX_FRAME_SIZE_HWORDS,
+            frame_timeout: DEFAULT_FRAME_TIMEOUT,
+            last_activity: None,
+            frame_timeouts: 0,
+            oversize_aborts: 0,
+            pixel_count_layout: PixelCountLayout::default(),
+        }
+    }
+
+    /// Create an engine whose watchdog enforces the supplied size and timeout
+    /// limits. These come straight from
+    /// [`FrameGrabberConfig`](crate::config::FrameGrabberConfig) so a deployment
+    /// can bound memory use and stall recovery without recompiling.
+    pub fn with_config(
+        max_frame_size_hwords: usize,
+        frame_timeout: Duration,
+        pixel_count_layout: PixelCountLayout,
+    ) -> Self {
+        Self {
+            max_frame_size_hwords,
+            frame_timeout,
+            pixel_count_layout,
+            ..Self::new()
         }
     }
 
@@ -105,6 +332,11 @@ impl FrameSyncEngine {
         (self.frames_completed, self.sync_errors, self.header_index_errors)
     }
 
+    /// Watchdog statistics: `(frame_timeouts, oversize_aborts)`.
+    pub fn watchdog_stats(&self) -> (u64, u64) {
+        (self.frame_timeouts, self.oversize_aborts)
+    }
+
     /// Check if an HWORD matches the Idle pattern
     fn is_idle_hword(chunk: &[u8]) -> bool {
         if chunk.len() != 12 {
@@ -162,9 +394,60 @@ impl FrameSyncEngine {
         }
     }
 
-    /// Process a 12-byte HWORD chunk
-    /// Returns Some(frame_data) when a complete frame is ready
-    pub fn process_hword(&mut self, chunk: &[u8; 12]) -> Option<Vec<u8>> {
+    /// Read the 16-bit register at index `reg` from the first header HWORD.
+    ///
+    /// Registers are packed 5 per header HWORD in bits 79:0; only indices that
+    /// live in this first HWORD (`0..5`) are readable at frame start, so higher
+    /// indices return `None`.
+    fn read_register(first_header_hword: &HWord, reg: usize) -> Option<u16> {
+        if reg >= 5 {
+            return None;
+        }
+        let data = first_header_hword.data_as_u128();
+        Some(((data >> (reg * 16)) & 0xFFFF) as u16)
+    }
+
+    /// Reconstruct the imaging pixel count from the configured register layout.
+    ///
+    /// Returns `None` (mode falls back to [`FrameMode::Unknown`]) when a required
+    /// register is not in the first header HWORD, or when the reconstructed count
+    /// would overrun `max_frame_size_hwords` - a corrupt count is logged and
+    /// rejected rather than driving an unbounded pixel collection.
+    fn reconstruct_num_pixels(&self, first_header_hword: &HWord) -> Option<usize> {
+        let count = match self.pixel_count_layout {
+            PixelCountLayout::Single16 { reg } => {
+                Self::read_register(first_header_hword, reg)? as u32
+            }
+            PixelCountLayout::Split16x16 { high_reg, low_reg } => {
+                let high = Self::read_register(first_header_hword, high_reg)? as u32;
+                let low = Self::read_register(first_header_hword, low_reg)? as u32;
+                (high << 16) | low
+            }
+            PixelCountLayout::Scaled { reg, factor } => {
+                let raw = Self::read_register(first_header_hword, reg)? as u32;
+                raw.saturating_mul(factor)
+            }
+        } as usize;
+
+        // A count of zero means 1-point scan (no NUM_PIXELS programmed).
+        let count = if count == 0 { 1 } else { count };
+
+        if count + HEADER_HWORDS_PER_FRAME > self.max_frame_size_hwords {
+            warn!("reconstructed pixel count {} exceeds max frame size {} HWORDs - falling back to Unknown",
+                  count, self.max_frame_size_hwords);
+            return None;
+        }
+
+        Some(count)
+    }
+
+    /// Process a 12-byte HWORD chunk.
+    ///
+    /// Returns `Some((frame_data, quality))` when a complete or salvaged frame
+    /// is ready. The [`FrameQuality`] records whether the frame was patched over
+    /// header gaps or cut short, so consumers can distinguish clean keyframes
+    /// from recovered ones.
+    pub fn process_hword(&mut self, chunk: &[u8; 12]) -> Option<(Vec<u8>, FrameQuality)> {
         // Parse the HWORD
         let hword = match HWord::from_bytes(chunk) {
             Ok(h) => h,
@@ -196,20 +479,18 @@ impl FrameSyncEngine {
                 if hword.control_bits.is_frame_start() {
                     debug!("ðŸ“¦ Frame start detected");
                     self.frame_buffer.clear();
+                    self.current_header_gaps = 0;
                     self.frame_buffer.extend_from_slice(chunk);
-                    
-                    // Try to extract expected pixel count from header
-                    let expected_pixels = Self::extract_num_pixels(&hword)
-                        .unwrap_or(1); // Default to 1-point scan
-                    
-                    self.current_mode = if expected_pixels == 1 {
-                        FrameMode::OnePointScan
-                    } else if expected_pixels == 5 {
-                        FrameMode::FivePointScan
-                    } else {
-                        FrameMode::Imaging { expected_pixels }
+
+                    // Reconstruct the pixel count per the configured register
+                    // layout and let FrameMode::detect pick the mode from it.
+                    self.current_mode = match self.reconstruct_num_pixels(&hword) {
+                        Some(expected_pixels) => {
+                            FrameMode::detect(HEADER_HWORDS_PER_FRAME, expected_pixels)
+                        }
+                        None => FrameMode::Unknown,
                     };
-                    
+
                     debug!("Frame mode: {:?}", self.current_mode);
                     
                     self.state = FrameSyncState::CollectingHeader {
@@ -223,49 +504,50 @@ impl FrameSyncEngine {
             FrameSyncState::CollectingHeader { count, last_index } => {
                 // Validate header index progression
                 if hword.control_bits.is_header() {
-                    if let Some(index) = Self::extract_header_index(&hword) {
-                        if let Some(last) = last_index {
-                            let expected = (last + 1) % 16; // Header index wraps at 16
-                            if index != expected && count < HEADER_HWORDS_PER_FRAME {
-                                warn!("âš ï¸ Header index mismatch: expected {}, got {} (HWORD {})", 
+                    let index = Self::extract_header_index(&hword);
+
+                    // Detect a gap in the 4-bit header index and, if it is small
+                    // enough, reconstruct the dropped HWORDs instead of aborting.
+                    let mut count = count;
+                    if let (Some(index), Some(last)) = (index, last_index) {
+                        let expected = (last + 1) % 16; // Header index wraps at 16
+                        if index != expected && count < HEADER_HWORDS_PER_FRAME {
+                            let missing = index.wrapping_sub(last.wrapping_add(1)) as usize % 16;
+                            if missing > 0
+                                && missing <= self.max_header_gap
+                                && count + missing <= HEADER_HWORDS_PER_FRAME
+                            {
+                                warn!("âš ï¸ Header gap of {} (last {}, got {}) - salvaging",
+                                      missing, last, index);
+                                for _ in 0..missing {
+                                    self.frame_buffer.extend_from_slice(&PLACEHOLDER_HEADER_HWORD);
+                                }
+                                count += missing;
+                                self.current_header_gaps += missing;
+                            } else {
+                                warn!("âš ï¸ Header index mismatch: expected {}, got {} (HWORD {})",
                                       expected, index, count);
-                                self.header_index_errors += 1;
                             }
+                            self.header_index_errors += 1;
                         }
-                        
-                        self.frame_buffer.extend_from_slice(chunk);
-                        
-                        if count + 1 >= HEADER_HWORDS_PER_FRAME {
-                            // Header complete, transition to pixel collection
-                            debug!("âœ… Header complete ({} HWORDs)", HEADER_HWORDS_PER_FRAME);
-                            self.state = FrameSyncState::CollectingPixels {
-                                header_count: count + 1,
-                                pixel_count: 0,
-                                expected_pixels: self.current_mode.expected_pixel_count(),
-                            };
-                        } else {
-                            self.state = FrameSyncState::CollectingHeader {
-                                count: count + 1,
-                                last_index: Some(index),
-                            };
-                        }
+                    }
+
+                    self.frame_buffer.extend_from_slice(chunk);
+                    count += 1;
+
+                    if count >= HEADER_HWORDS_PER_FRAME {
+                        // Header complete, transition to pixel collection
+                        debug!("âœ… Header complete ({} HWORDs)", HEADER_HWORDS_PER_FRAME);
+                        self.state = FrameSyncState::CollectingPixels {
+                            header_count: count,
+                            pixel_count: 0,
+                            expected_pixels: self.current_mode.expected_pixel_count(),
+                        };
                     } else {
-                        // No header index found, just count
-                        self.frame_buffer.extend_from_slice(chunk);
-                        
-                        if count + 1 >= HEADER_HWORDS_PER_FRAME {
-                            debug!("âœ… Header complete ({} HWORDs)", HEADER_HWORDS_PER_FRAME);
-                            self.state = FrameSyncState::CollectingPixels {
-                                header_count: count + 1,
-                                pixel_count: 0,
-                                expected_pixels: self.current_mode.expected_pixel_count(),
-                            };
-                        } else {
-                            self.state = FrameSyncState::CollectingHeader {
-                                count: count + 1,
-                                last_index,
-                            };
-                        }
+                        self.state = FrameSyncState::CollectingHeader {
+                            count,
+                            last_index: index.or(last_index),
+                        };
                     }
                 } else if hword.control_bits.is_pixel() {
                     // Premature transition to pixels - header might be shorter than expected
@@ -290,11 +572,8 @@ impl FrameSyncEngine {
                         info!("âœ… Frame complete: {} header + {} pixel = {} total HWORDs ({} bytes)",
                               header_count, pixel_count + 1, total_hwords, self.frame_buffer.len());
                         
-                        self.frames_completed += 1;
-                        self.state = FrameSyncState::WaitingForFrame;
-                        
-                        // Return the completed frame
-                        return Some(self.frame_buffer.clone());
+                        let quality = FrameQuality::new(self.current_header_gaps, 0);
+                        return Some(self.emit_frame(quality));
                     } else {
                         self.state = FrameSyncState::CollectingPixels {
                             header_count,
@@ -307,14 +586,23 @@ impl FrameSyncEngine {
                     warn!("âš ï¸ Incomplete frame: expected {} pixels, got {} (starting new frame)",
                           expected_pixels, pixel_count);
                     self.sync_errors += 1;
-                    
-                    // Start new frame
-                    self.frame_buffer.clear();
-                    self.frame_buffer.extend_from_slice(chunk);
-                    self.state = FrameSyncState::CollectingHeader {
-                        count: 1,
-                        last_index: Some(0),
-                    };
+
+                    let pixel_shortfall = expected_pixels.saturating_sub(pixel_count);
+                    let quality = FrameQuality::new(self.current_header_gaps, pixel_shortfall);
+                    let salvaged = self.emit_frame(quality);
+
+                    // Start the new frame from the interrupting FrameStart, unless
+                    // the degraded run just forced a resync.
+                    if self.state == FrameSyncState::WaitingForFrame {
+                        self.frame_buffer.clear();
+                        self.current_header_gaps = 0;
+                        self.frame_buffer.extend_from_slice(chunk);
+                        self.state = FrameSyncState::CollectingHeader {
+                            count: 1,
+                            last_index: Some(0),
+                        };
+                    }
+                    return Some(salvaged);
                 }
                 None
             }
@@ -327,7 +615,135 @@ impl FrameSyncEngine {
         }
     }
 
-    /// Get current frame buffer (for debugging)
+    /// Finalize the current frame buffer, update counters, and decide whether a
+    /// run of degraded frames should force a resync.
+    fn emit_frame(&mut self, quality: FrameQuality) -> (Vec<u8>, FrameQuality) {
+        self.frames_completed += 1;
+
+        if quality.degraded {
+            self.consecutive_degraded += 1;
+        } else {
+            self.consecutive_degraded = 0;
+        }
+
+        if self.consecutive_degraded >= self.max_consecutive_degraded {
+            warn!("consecutive degraded frames ({}) - forcing resync",
+                  self.consecutive_degraded);
+            self.consecutive_degraded = 0;
+            self.state = FrameSyncState::WaitingForSync;
+        } else {
+            self.state = FrameSyncState::WaitingForFrame;
+        }
+
+        (self.frame_buffer.clone(), quality)
+    }
+
+    /// Drop back to [`FrameSyncState::WaitingForSync`] so the engine re-locks on
+    /// the next Idle HWORD. A downstream consumer calls this to request a fresh
+    /// keyframe after detecting too much degradation.
+    pub fn request_resync(&mut self) {
+        self.frame_buffer.clear();
+        self.current_header_gaps = 0;
+        self.consecutive_degraded = 0;
+        self.state = FrameSyncState::WaitingForSync;
+    }
+
+    /// Like [`process_hword`](Self::process_hword) but yields a typed
+    /// [`ParsedFrame`] instead of the raw buffer, carrying the engine's quality
+    /// report into the parsed value. Returns `None` while no frame is ready, and
+    /// logs and drops a frame that fails to parse.
+    pub fn process_hword_parsed(&mut self, chunk: &[u8; 12]) -> Option<ParsedFrame> {
+        let (bytes, quality) = self.process_hword(chunk)?;
+        match ParsedFrame::from_bytes(&bytes) {
+            Ok(mut frame) => {
+                frame.quality = quality;
+                Some(frame)
+            }
+            Err(e) => {
+                warn!("failed to parse completed frame: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Process a 12-byte HWORD, stamping `now` as the moment it arrived.
+    ///
+    /// Before handling the chunk the watchdog checks the in-progress frame: if it
+    /// has stalled past `frame_timeout` or grown beyond `max_frame_size_hwords`,
+    /// whatever was collected is aborted and returned as a degraded frame, and the
+    /// triggering chunk is dropped. Otherwise this behaves like
+    /// [`process_hword`](Self::process_hword) while tracking activity for `tick`.
+    pub fn process_hword_at(&mut self, chunk: &[u8; 12], now: Instant) -> Option<(Vec<u8>, FrameQuality)> {
+        if self.is_collecting() {
+            if let Some(last) = self.last_activity {
+                if now.duration_since(last) > self.frame_timeout {
+                    warn!("frame timed out after {:?} - aborting", self.frame_timeout);
+                    self.frame_timeouts += 1;
+                    return Some(self.abort_degraded());
+                }
+            }
+            if self.frame_buffer.len() / 12 > self.max_frame_size_hwords {
+                warn!("frame exceeded {} HWORDs - aborting", self.max_frame_size_hwords);
+                self.oversize_aborts += 1;
+                return Some(self.abort_degraded());
+            }
+        }
+
+        let was_collecting = self.is_collecting();
+        let len_before = self.frame_buffer.len();
+        let result = self.process_hword(chunk);
+        // Only a chunk that advanced the frame (started it or added to its
+        // buffer) refreshes the activity stamp; ignored chunks do not.
+        self.last_activity = if !self.is_collecting() {
+            None
+        } else if !was_collecting || result.is_some() || self.frame_buffer.len() != len_before {
+            Some(now)
+        } else {
+            self.last_activity
+        };
+        result
+    }
+
+    /// Advance the watchdog without feeding new data.
+    ///
+    /// Call this on an idle tick so a frame that stops arriving mid-stream is
+    /// still aborted and flushed as a degraded frame rather than lingering.
+    pub fn tick(&mut self, now: Instant) -> Option<(Vec<u8>, FrameQuality)> {
+        if self.is_collecting() {
+            if let Some(last) = self.last_activity {
+                if now.duration_since(last) > self.frame_timeout {
+                    warn!("frame timed out after {:?} - aborting", self.frame_timeout);
+                    self.frame_timeouts += 1;
+                    return Some(self.abort_degraded());
+                }
+            }
+        }
+        None
+    }
+
+    /// True while a frame is being assembled (header or pixel collection).
+    fn is_collecting(&self) -> bool {
+        matches!(
+            self.state,
+            FrameSyncState::CollectingHeader { .. } | FrameSyncState::CollectingPixels { .. }
+        )
+    }
+
+    /// Abort the in-progress frame, flushing it as a degraded frame.
+    fn abort_degraded(&mut self) -> (Vec<u8>, FrameQuality) {
+        let pixel_shortfall = match self.state {
+            FrameSyncState::CollectingPixels { pixel_count, expected_pixels, .. } => {
+                expected_pixels.saturating_sub(pixel_count)
+            }
+            _ => 0,
+        };
+        let mut quality = FrameQuality::new(self.current_header_gaps, pixel_shortfall);
+        quality.degraded = true;
+        self.last_activity = None;
+        self.emit_frame(quality)
+    }
+
+        /// Get current frame buffer (for debugging)
     pub fn current_buffer(&self) -> &[u8] {
         &self.frame_buffer
     }
@@ -3,14 +3,25 @@ GUI implementation for the frame grabber application.
 */
 
 use crate::config::AppConfig;
-use crate::capture::SimpleFrameGrabber;
+use crate::capture::{CaptureStats, SimpleFrameGrabber};
 use eframe::egui;
+use egui_plot::{Plot, PlotPoints, Points};
+use shared::clock::{Clocks, SystemClocks, Timestamp};
+use shared::coordinates::CoordinateData;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
 use std::sync::Arc;
 use std::thread;
 use tracing::{info, error};
 
+/// Bounded depth of the capture→GUI stats channel.
+///
+/// A handful of slots is plenty: the GUI only ever renders the most recent
+/// frame, so older snapshots are expendable and the bound keeps a stalled UI
+/// from back-pressuring capture.
+const STATS_CHANNEL_DEPTH: usize = 8;
+
 /// Main GUI application state
 pub struct FrameGrabberGui {
     config: AppConfig,
@@ -25,7 +36,17 @@ pub struct FrameGrabberGui {
     status_message: String,
     packet_count: u64,
     frame_count: u64,
-    
+
+    // Timing
+    clock: Arc<dyn Clocks>,
+    last_frame_timestamp: Option<Timestamp>,
+    fps: f64,
+
+    // Live stats / preview
+    stats_rx: Option<Receiver<CaptureStats>>,
+    latest_coords: Option<CoordinateData>,
+    preview_decimation: usize,
+
     // Temporary UI values
     temp_bind_addr: String,
     temp_port: String,
@@ -47,9 +68,66 @@ impl FrameGrabberGui {
             status_message: "Ready".to_string(),
             packet_count: 0,
             frame_count: 0,
+            clock: Arc::new(SystemClocks::new()),
+            last_frame_timestamp: None,
+            fps: 0.0,
+            stats_rx: None,
+            latest_coords: None,
+            preview_decimation: 1,
         }
     }
-    
+
+    /// Update the measured frame rate from a freshly completed frame's
+    /// timestamp, using the monotonic delta from the previous frame rather than
+    /// the repaint interval.
+    fn record_frame_timestamp(&mut self, timestamp: Timestamp) {
+        if let Some(prev) = self.last_frame_timestamp {
+            let delta_nanos = timestamp.monotonic_since(&prev);
+            if delta_nanos > 0 {
+                self.fps = 1_000_000_000.0 / delta_nanos as f64;
+            }
+        }
+        self.last_frame_timestamp = Some(timestamp);
+    }
+
+    /// Drain any pending capture stats, refreshing the counters, frame rate, and
+    /// the most-recent coordinate snapshot used by the preview.
+    fn drain_stats(&mut self) {
+        let Some(rx) = &self.stats_rx else { return };
+        let mut snapshots = Vec::new();
+        while let Ok(stats) = rx.try_recv() {
+            snapshots.push(stats);
+        }
+        for stats in snapshots {
+            self.packet_count = stats.packet_count;
+            self.frame_count = stats.frame_count;
+            if stats.latest.is_some() {
+                self.latest_coords = stats.latest;
+            }
+            // Stamp arrival through the injected clock so FPS stays testable.
+            let now = self.clock.now();
+            self.record_frame_timestamp(now);
+        }
+    }
+
+    /// Build the decimated `[x, y]` scatter for the preview plot from the most
+    /// recent frame's coordinates. The decimation slider keeps the plot
+    /// responsive on dense frames by sampling every nth point.
+    fn preview_points(&self) -> Vec<[f64; 2]> {
+        let Some(coords) = &self.latest_coords else {
+            return Vec::new();
+        };
+        let step = self.preview_decimation.max(1);
+        let (Some(xs), Some(ys)) = (coords.x_values(), coords.y_values()) else {
+            return Vec::new();
+        };
+        xs.iter()
+            .zip(ys.iter())
+            .step_by(step)
+            .map(|(&x, &y)| [x, y])
+            .collect()
+    }
+
     /// Start frame capture
     fn start_capture(&mut self) {
         if self.capture_running {
@@ -73,9 +151,22 @@ impl FrameGrabberGui {
             false, // decode_mode - not supported in GUI yet
         );
         
+        grabber.set_pixel_count_layout(self.config.framegrabber.pixel_count_layout);
+        grabber.set_header_layout(self.config.framegrabber.header_layout);
+
+        // Wire up the live stats channel so the status panel and preview update
+        // while capturing.
+        let (stats_tx, stats_rx) = mpsc::sync_channel(STATS_CHANNEL_DEPTH);
+        grabber.set_stats_sender(stats_tx);
+        self.stats_rx = Some(stats_rx);
+        self.packet_count = 0;
+        self.frame_count = 0;
+        self.last_frame_timestamp = None;
+        self.fps = 0.0;
+
         let running_flag = grabber.get_running_flag();
         self.running_flag = Some(Arc::clone(&running_flag));
-        
+
         // Start capture in background thread
         let handle = thread::spawn(move || {
             match grabber.start() {
@@ -118,6 +209,9 @@ impl FrameGrabberGui {
 
 impl eframe::App for FrameGrabberGui {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Pull any live stats published by the capture thread.
+        self.drain_stats();
+
         // Check if capture thread has finished
         if self.capture_running {
             if let Some(handle) = &self.capture_thread {
@@ -238,6 +332,37 @@ impl eframe::App for FrameGrabberGui {
                     ui.label("Frames:");
                     ui.label(format!("{}", self.frame_count));
                 });
+
+                ui.horizontal(|ui| {
+                    ui.label("FPS:");
+                    ui.label(format!("{:.1}", self.fps));
+                });
+            });
+
+            ui.separator();
+
+            // Live point-cloud preview of the most recent frame.
+            ui.group(|ui| {
+                ui.label("🛰 Point Cloud Preview");
+
+                ui.horizontal(|ui| {
+                    ui.label("Decimation:");
+                    ui.add(egui::Slider::new(&mut self.preview_decimation, 1..=64));
+                });
+
+                let points = self.preview_points();
+                if points.is_empty() {
+                    ui.label("No frame data yet");
+                } else {
+                    Plot::new("point_cloud_preview")
+                        .view_aspect(1.0)
+                        .height(260.0)
+                        .show(ui, |plot_ui| {
+                            plot_ui.points(
+                                Points::new(PlotPoints::from(points)).radius(1.5),
+                            );
+                        });
+                }
             });
             
             ui.separator();
@@ -0,0 +1,354 @@
+/*!
+Offline frame/HWORD inspector REPL.
+
+`framegrabber inspect <file.dsql>` opens a recorded capture and walks it one
+HWORD at a time under operator control. It is modeled on a line-oriented machine
+debugger: a prompt reads a command, runs it, and an empty line repeats the last
+command — so stepping through a stream is just `Enter, Enter, Enter`, and a
+`step 100` followed by blank lines keeps stepping 100 at a time.
+
+Commands cover the three things raw `HWordError` cannot explain on their own:
+where a frame actually starts, which words fail parity, and what a word's decoded
+fields really are. Breakpoints halt `continue`/`trace` on a control-bit pattern
+(e.g. the next `FirstHeader`) or on the first word whose parity check fails, so a
+malformed frame can be cornered without scrolling past thousands of good words.
+
+Stored records are decoded through [`crate::codec::decode`] first, so compressed
+and legacy-raw captures inspect identically.
+*/
+
+use std::io::{self, BufRead, Write};
+
+use shared::hword::{ControlBits, Endianness, HWord};
+
+use crate::codec;
+
+/// Size of one HWORD on disk, in bytes.
+const HWORD: usize = 12;
+
+/// A breakpoint that halts `continue`/`trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Breakpoint {
+    /// Halt on the next word whose parity check fails.
+    ParityFail,
+    /// Halt on the next word with these control bits.
+    Control(ControlBits),
+}
+
+/// A parsed REPL command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    /// Advance `n` words, printing each.
+    Step(usize),
+    /// Run until a breakpoint or the end of the stream.
+    Continue,
+    /// Like `Continue`, but print every word as it is visited.
+    Trace,
+    /// Print the word at the cursor.
+    Print,
+    /// Jump the cursor to an absolute word index.
+    Goto(usize),
+    /// Add a breakpoint.
+    Break(Breakpoint),
+    /// Clear all breakpoints.
+    ClearBreaks,
+    /// Show cursor position and configured breakpoints.
+    Info,
+    /// Print command help.
+    Help,
+    /// Leave the REPL.
+    Quit,
+}
+
+impl Command {
+    /// Parse a command line. Returns `Err` with a user-facing message on a bad
+    /// command, `Ok(None)` for an empty line (the caller repeats the last one).
+    fn parse(line: &str) -> Result<Option<Command>, String> {
+        let mut tokens = line.split_whitespace();
+        let Some(verb) = tokens.next() else {
+            return Ok(None);
+        };
+        let cmd = match verb {
+            "step" | "s" => {
+                let n = tokens.next().map(parse_count).transpose()?.unwrap_or(1);
+                Command::Step(n)
+            }
+            "continue" | "c" => Command::Continue,
+            "trace" | "t" => Command::Trace,
+            "print" | "p" => Command::Print,
+            "goto" | "g" => {
+                let n = tokens.next().ok_or("goto needs a word index")?;
+                Command::Goto(parse_count(n)?)
+            }
+            "break" | "b" => {
+                let kind = tokens.next().ok_or("break needs 'parity' or 'control <NAME>'")?;
+                match kind {
+                    "parity" => Command::Break(Breakpoint::ParityFail),
+                    "control" => {
+                        let name = tokens.next().ok_or("break control needs a control-bit name")?;
+                        Command::Break(Breakpoint::Control(parse_control(name)?))
+                    }
+                    other => return Err(format!("unknown breakpoint kind '{other}'")),
+                }
+            }
+            "clear" => Command::ClearBreaks,
+            "info" | "i" => Command::Info,
+            "help" | "h" | "?" => Command::Help,
+            "quit" | "q" => Command::Quit,
+            other => return Err(format!("unknown command '{other}' (try 'help')")),
+        };
+        Ok(Some(cmd))
+    }
+}
+
+fn parse_count(token: &str) -> Result<usize, String> {
+    token.parse().map_err(|_| format!("invalid count '{token}'"))
+}
+
+/// Parse a control-bit name as spelled in the `ControlBits` enum.
+fn parse_control(name: &str) -> Result<ControlBits, String> {
+    let lower = name.to_ascii_lowercase();
+    let matched = [
+        ("reserved0", ControlBits::Reserved0),
+        ("reserved1", ControlBits::Reserved1),
+        ("firstheader", ControlBits::FirstHeader),
+        ("subsequentheader", ControlBits::SubsequentHeader),
+        ("firstpixel", ControlBits::FirstPixel),
+        ("subsequentpixel", ControlBits::SubsequentPixel),
+        ("reserved6", ControlBits::Reserved6),
+        ("idle", ControlBits::Idle),
+    ]
+    .into_iter()
+    .find(|(n, _)| *n == lower);
+    matched.map(|(_, c)| c).ok_or_else(|| format!("unknown control-bit name '{name}'"))
+}
+
+/// The interactive inspector over a decoded HWORD stream.
+pub struct Inspector {
+    words: Vec<[u8; HWORD]>,
+    endianness: Endianness,
+    cursor: usize,
+    breakpoints: Vec<Breakpoint>,
+    last_command: Option<Command>,
+}
+
+impl Inspector {
+    /// Load and decode a `.dsql` file into an inspector positioned at word 0.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let raw = std::fs::read(path)?;
+        let decoded = codec::decode(&raw)?;
+        let words = decoded
+            .chunks_exact(HWORD)
+            .map(|c| c.try_into().expect("chunks_exact yields 12-byte words"))
+            .collect();
+        Ok(Self {
+            words,
+            endianness: Endianness::Big,
+            cursor: 0,
+            breakpoints: Vec::new(),
+            last_command: None,
+        })
+    }
+
+    /// Run the REPL against stdin/stdout until the user quits or input ends.
+    pub fn run(&mut self) -> io::Result<()> {
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+        println!(
+            "🔎 Inspecting {} HWORDs. Type 'help' for commands, empty line repeats last.",
+            self.words.len()
+        );
+        self.describe(self.cursor);
+
+        let mut input = stdin.lock();
+        let mut line = String::new();
+        loop {
+            write!(stdout, "hword[{}]> ", self.cursor)?;
+            stdout.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                break; // EOF
+            }
+
+            let command = match Command::parse(line.trim_end()) {
+                Ok(Some(cmd)) => Some(cmd),
+                Ok(None) => self.last_command.clone(), // empty line repeats
+                Err(e) => {
+                    println!("error: {e}");
+                    continue;
+                }
+            };
+            let Some(command) = command else {
+                println!("(no previous command)");
+                continue;
+            };
+
+            if command == Command::Quit {
+                break;
+            }
+            self.execute(&command);
+            self.last_command = Some(command);
+        }
+        Ok(())
+    }
+
+    /// Apply a single command to the inspector state.
+    fn execute(&mut self, command: &Command) {
+        match command {
+            Command::Step(n) => {
+                for _ in 0..*n {
+                    if self.cursor >= self.words.len() {
+                        println!("(end of stream)");
+                        break;
+                    }
+                    self.describe(self.cursor);
+                    self.cursor += 1;
+                }
+            }
+            Command::Continue => self.run_until_break(false),
+            Command::Trace => self.run_until_break(true),
+            Command::Print => {
+                if self.cursor < self.words.len() {
+                    self.describe(self.cursor);
+                } else {
+                    println!("(end of stream)");
+                }
+            }
+            Command::Goto(n) => {
+                self.cursor = (*n).min(self.words.len());
+                println!("→ word {}", self.cursor);
+            }
+            Command::Break(bp) => {
+                self.breakpoints.push(*bp);
+                println!("breakpoint added: {bp:?}");
+            }
+            Command::ClearBreaks => {
+                self.breakpoints.clear();
+                println!("breakpoints cleared");
+            }
+            Command::Info => {
+                println!("cursor: word {} / {}", self.cursor, self.words.len());
+                if self.breakpoints.is_empty() {
+                    println!("breakpoints: none");
+                } else {
+                    for (i, bp) in self.breakpoints.iter().enumerate() {
+                        println!("  [{i}] {bp:?}");
+                    }
+                }
+            }
+            Command::Help => Self::print_help(),
+            Command::Quit => {}
+        }
+    }
+
+    /// Advance from the cursor until a breakpoint hits or the stream ends. In
+    /// `trace` mode every visited word is printed; otherwise only the word that
+    /// triggers the breakpoint is.
+    fn run_until_break(&mut self, trace: bool) {
+        while self.cursor < self.words.len() {
+            let parsed = self.parse_at(self.cursor);
+            if trace {
+                self.describe(self.cursor);
+            }
+            if let Some(bp) = self.hit_breakpoint(&parsed) {
+                if !trace {
+                    self.describe(self.cursor);
+                }
+                println!("⛔ breakpoint hit: {bp:?}");
+                self.cursor += 1;
+                return;
+            }
+            self.cursor += 1;
+        }
+        println!("(end of stream)");
+    }
+
+    /// Which configured breakpoint, if any, the parsed word triggers.
+    fn hit_breakpoint(&self, parsed: &Result<HWord, String>) -> Option<Breakpoint> {
+        self.breakpoints.iter().copied().find(|bp| match bp {
+            Breakpoint::ParityFail => match parsed {
+                Ok(hword) => !hword.verify_parity(),
+                Err(_) => true, // an unparseable word is certainly "bad"
+            },
+            Breakpoint::Control(target) => {
+                matches!(parsed, Ok(hword) if hword.control_bits == *target)
+            }
+        })
+    }
+
+    /// Parse the word at `index`, mapping parse failures to a message.
+    fn parse_at(&self, index: usize) -> Result<HWord, String> {
+        HWord::from_bytes_with_order(&self.words[index], self.endianness).map_err(|e| e.to_string())
+    }
+
+    /// Print a one-screen annotation of the word at `index`.
+    fn describe(&self, index: usize) {
+        if index >= self.words.len() {
+            println!("(end of stream)");
+            return;
+        }
+        let bytes = &self.words[index];
+        match self.parse_at(index) {
+            Ok(hword) => {
+                let data = hword.data_as_u128();
+                let parity = if hword.verify_parity() { "ok" } else { "FAIL" };
+                println!("#{index:06}  {:?}  parity={parity}", hword.control_bits);
+                println!("    data = 0x{data:023X}");
+                println!("    bits = {data:092b}");
+            }
+            Err(e) => {
+                println!("#{index:06}  <unparseable: {e}>");
+                print!("    raw  =");
+                for b in bytes {
+                    print!(" {b:02X}");
+                }
+                println!();
+            }
+        }
+    }
+
+    fn print_help() {
+        println!("commands:");
+        println!("  step [N] | s     advance N words (default 1), printing each");
+        println!("  continue | c     run until a breakpoint or end of stream");
+        println!("  trace | t        like continue, printing every word visited");
+        println!("  print | p        print the word at the cursor");
+        println!("  goto N | g N     jump the cursor to word N");
+        println!("  break parity     halt on the next parity failure");
+        println!("  break control X  halt on the next word with control bits X");
+        println!("  clear            remove all breakpoints");
+        println!("  info | i         show cursor position and breakpoints");
+        println!("  help | h | ?     show this help");
+        println!("  quit | q         leave the inspector");
+        println!("  <empty line>     repeat the last command");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_common_commands() {
+        assert_eq!(Command::parse("").unwrap(), None);
+        assert_eq!(Command::parse("step").unwrap(), Some(Command::Step(1)));
+        assert_eq!(Command::parse("s 42").unwrap(), Some(Command::Step(42)));
+        assert_eq!(Command::parse("c").unwrap(), Some(Command::Continue));
+        assert_eq!(
+            Command::parse("break parity").unwrap(),
+            Some(Command::Break(Breakpoint::ParityFail))
+        );
+        assert_eq!(
+            Command::parse("break control FirstHeader").unwrap(),
+            Some(Command::Break(Breakpoint::Control(ControlBits::FirstHeader)))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(Command::parse("frob").is_err());
+        assert!(Command::parse("goto").is_err());
+        assert!(Command::parse("step abc").is_err());
+        assert!(Command::parse("break control Nope").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+/*!
+Retained in-memory ring-buffer log sink.
+
+Live output mode writes the binary frame stream to stdout, so `main` historically
+disabled the `tracing` subscriber entirely to keep that stream clean — which also
+threw away every parse diagnostic (`ParityCheckFailed`, `InvalidControlBits`) the
+run produced. [`RingBufferLayer`] is a `tracing` layer that is installed in *all*
+modes: it records events into a bounded ring held in memory, never touching
+stdout, and evicts the oldest entry when the ring is full. A headless live
+capture can then be diagnosed after the fact — the buffer is flushed to stderr on
+`Ctrl+C` shutdown via [`flush`].
+
+Alongside the raw records it keeps a handful of per-category counters (parity
+failures, invalid control bits, frame boundaries seen, and warning/error totals)
+so an operator gets an at-a-glance health summary without scrolling the whole
+ring.
+*/
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Number of records retained in the ring before the oldest is evicted.
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+/// Per-category counters surfaced in the flush summary.
+#[derive(Debug, Default)]
+pub struct LogCounters {
+    /// Events mentioning an HWORD parity failure.
+    pub parity_failures: AtomicU64,
+    /// Events mentioning invalid control bits.
+    pub invalid_control_bits: AtomicU64,
+    /// Completed-frame / frame-boundary events.
+    pub frame_boundaries: AtomicU64,
+    /// Total `WARN` events.
+    pub warnings: AtomicU64,
+    /// Total `ERROR` events.
+    pub errors: AtomicU64,
+}
+
+/// A single retained log record.
+struct Record {
+    level: Level,
+    target: String,
+    message: String,
+}
+
+/// Bounded ring of recent log records plus category counters.
+pub struct LogBuffer {
+    records: Mutex<VecDeque<Record>>,
+    capacity: usize,
+    counters: LogCounters,
+}
+
+impl LogBuffer {
+    /// Create an empty buffer retaining up to `capacity` records.
+    pub fn new(capacity: usize) -> Arc<Self> {
+        Arc::new(Self {
+            records: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+            capacity: capacity.max(1),
+            counters: LogCounters::default(),
+        })
+    }
+
+    /// Append a record, evicting the oldest when the ring is full.
+    fn push(&self, record: Record) {
+        // Classify before the record is moved into the ring.
+        let lower = record.message.to_ascii_lowercase();
+        if lower.contains("parity") {
+            self.counters.parity_failures.fetch_add(1, Ordering::Relaxed);
+        }
+        if lower.contains("control bit") {
+            self.counters.invalid_control_bits.fetch_add(1, Ordering::Relaxed);
+        }
+        if lower.contains("completed frame") || lower.contains("frame boundary") {
+            self.counters.frame_boundaries.fetch_add(1, Ordering::Relaxed);
+        }
+        match record.level {
+            Level::WARN => {
+                self.counters.warnings.fetch_add(1, Ordering::Relaxed);
+            }
+            Level::ERROR => {
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Dump the counter summary and every retained record to stderr.
+    pub fn flush_to_stderr(&self) {
+        let c = &self.counters;
+        eprintln!("──── retained log buffer ────");
+        eprintln!(
+            "counters: {} parity failures, {} invalid control bits, {} frame boundaries, {} warnings, {} errors",
+            c.parity_failures.load(Ordering::Relaxed),
+            c.invalid_control_bits.load(Ordering::Relaxed),
+            c.frame_boundaries.load(Ordering::Relaxed),
+            c.warnings.load(Ordering::Relaxed),
+            c.errors.load(Ordering::Relaxed),
+        );
+        let records = self.records.lock().unwrap();
+        for record in records.iter() {
+            eprintln!("[{:>5} {}] {}", record.level, record.target, record.message);
+        }
+        eprintln!("──── {} records retained ────", records.len());
+    }
+
+    /// Access the raw category counters.
+    pub fn counters(&self) -> &LogCounters {
+        &self.counters
+    }
+}
+
+/// A `tracing` layer that records events into a shared [`LogBuffer`].
+pub struct RingBufferLayer {
+    buffer: Arc<LogBuffer>,
+}
+
+impl RingBufferLayer {
+    /// Build a layer writing into `buffer`.
+    pub fn new(buffer: Arc<LogBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+/// Visitor that pulls the `message` field out of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let meta = event.metadata();
+        self.buffer.push(Record {
+            level: *meta.level(),
+            target: meta.target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Process-wide handle so the `Ctrl+C` path can flush without threading the
+/// buffer through every call site.
+static GLOBAL: OnceLock<Arc<LogBuffer>> = OnceLock::new();
+
+/// Install the ring-buffer layer as part of the global subscriber.
+///
+/// The ring is always captured; `stderr_fmt` additionally attaches the classic
+/// human-readable stderr formatter (disabled in live mode to keep stdout clean).
+/// Safe to call once at startup; later calls are ignored.
+pub fn install(stderr_fmt: bool) {
+    use tracing_subscriber::prelude::*;
+
+    let buffer = LogBuffer::new(DEFAULT_CAPACITY);
+    if GLOBAL.set(Arc::clone(&buffer)).is_err() {
+        return; // already installed
+    }
+
+    let registry = tracing_subscriber::registry().with(RingBufferLayer::new(buffer));
+    if stderr_fmt {
+        registry
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+            .init();
+    } else {
+        registry.init();
+    }
+}
+
+/// Flush the globally installed ring buffer to stderr, if one was installed.
+pub fn flush() {
+    if let Some(buffer) = GLOBAL.get() {
+        buffer.flush_to_stderr();
+    }
+}
@@ -32,16 +32,25 @@ framegrabber --cli --live --port 12345
 */
 
 use std::path::PathBuf;
-use tracing_subscriber;
 use clap::{Parser, Subcommand};
 
 mod config;
+mod config_store;
 mod gui;
 mod capture;
+mod codec;
+mod compression;
+mod inspect;
+mod log_buffer;
+mod output_sink;
+mod replay;
+mod frame_sync;
+mod ring_buffer;
 
 use config::AppConfig;
 use gui::FrameGrabberGui;
 use capture::SimpleFrameGrabber;
+use replay::{ReplayTarget, SimpleFrameReplayer};
 
 #[derive(Parser)]
 #[command(name = "framegrabber")]
@@ -87,34 +96,85 @@ enum Commands {
         /// Enable live decoding (output decoded coordinates instead of raw HWORD data)
         #[arg(long)]
         decode: bool,
+
+        /// Publish live frames to a named shared-memory ring instead of stdout
+        #[arg(long)]
+        shm: Option<String>,
+
+        /// Block codec for stored frames: none, zstd, or lz4
+        #[arg(long, default_value = "none")]
+        compress: codec::FrameCodec,
+
+        /// Run the idle/header pre-pass before the storage codec
+        #[arg(long)]
+        prepass: bool,
+
+        /// How the imaging pixel count is decoded from the header registers:
+        /// `single16:<reg>`, `split16x16:<high>,<low>`, or `scaled:<reg>,<factor>`
+        #[arg(long, default_value = "single16:2")]
+        pixel_count_layout: config::PixelCountLayout,
     },
     
+    /// Replay a recorded session of .dsql files
+    Replay {
+        /// Session directory containing NNNNNNNN.dsql files
+        #[arg(short, long)]
+        source_dir: String,
+
+        /// Re-emit frames as UDP datagrams to this address (omit for live output)
+        #[arg(long)]
+        target_addr: Option<String>,
+
+        /// UDP target port (used with --target-addr)
+        #[arg(long, default_value = "12345")]
+        target_port: u16,
+
+        /// Playback frame rate (0 = as fast as possible)
+        #[arg(long, default_value = "10.0")]
+        fps: f64,
+
+        /// Replay the session on a loop
+        #[arg(long)]
+        loop_playback: bool,
+
+        /// Decode coordinates when replaying to live output
+        #[arg(long)]
+        decode: bool,
+    },
+
     /// Generate configuration file
     Config {
         /// Output path for configuration file
         #[arg(short, long, default_value = "framegrabber.toml")]
         output: PathBuf,
     },
+
+    /// Walk a recorded .dsql file HWORD by HWORD in an interactive debugger
+    Inspect {
+        /// Path to the captured .dsql file to inspect
+        file: String,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     // Check if we're in live mode - if so, disable logging completely
-    let is_live_mode = matches!(cli.command, Some(Commands::Capture { live: true, .. }));
+    let is_live_mode = matches!(cli.command, Some(Commands::Capture { live: true, shm: None, .. }))
+        // Replaying to live output (no UDP target) also writes the stream to
+        // stdout, so keep logging off stdout there too.
+        || matches!(cli.command, Some(Commands::Replay { target_addr: None, .. }));
 
-    if !is_live_mode {
-        // Initialize logging to stderr to keep stdout clean for binary data
-        tracing_subscriber::fmt()
-            .with_writer(std::io::stderr)
-            .init();
-    }
+    // Always capture diagnostics into the retained in-memory ring buffer, even in
+    // live mode where the human-readable stderr formatter stays off to keep stdout
+    // clean for the binary stream. The ring is dumped to stderr on Ctrl+C.
+    log_buffer::install(!is_live_mode);
 
     
     match cli.command {
-        Some(Commands::Capture { bind_addr, port, output_dir, live, debug, decode }) => {
+        Some(Commands::Capture { bind_addr, port, output_dir, live, debug, decode, shm, compress, prepass, pixel_count_layout }) => {
             // In live mode, suppress all stdout output to keep binary stream clean
-            if live {
+            if live && shm.is_none() {
                 // Disable all logging to stdout when in live mode
                 println!("🚀 Starting frame capture (DEBUG mode)");
                 if debug {
@@ -124,13 +184,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             // Command-line capture mode
-            run_capture_cli(bind_addr, port, output_dir, !live, live, debug, decode)
+            run_capture_cli(bind_addr, port, output_dir, !live, live, debug, decode, shm, compress, prepass, pixel_count_layout)
         }
         
+        Some(Commands::Replay { source_dir, target_addr, target_port, fps, loop_playback, decode }) => {
+            run_replay(source_dir, target_addr, target_port, fps, loop_playback, decode)
+        }
+
         Some(Commands::Config { output }) => {
             // Generate configuration file
             generate_config_file(output)
         }
+
+        Some(Commands::Inspect { file }) => run_inspect(file),
         
         None => {
             if cli.cli {
@@ -153,6 +219,10 @@ fn run_capture_cli(
     live_output: bool,
     debug_mode: bool,
     decode_mode: bool,
+    shm: Option<String>,
+    compress: codec::FrameCodec,
+    prepass: bool,
+    pixel_count_layout: config::PixelCountLayout,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if debug_mode {
         println!("🚀 Starting frame capture (DEBUG mode)");
@@ -178,55 +248,137 @@ fn run_capture_cli(
         debug_mode,
         decode_mode,
     );
-    
+
+    if let Some(name) = shm {
+        grabber.set_output_sink(output_sink::OutputSinkConfig::SharedMemory {
+            name,
+            slot_count: output_sink::OutputSinkConfig::DEFAULT_SLOT_COUNT,
+            slot_size: output_sink::OutputSinkConfig::DEFAULT_SLOT_SIZE,
+        });
+    }
+
+    grabber.set_codec(compress, prepass);
+    grabber.set_pixel_count_layout(pixel_count_layout);
+
     // Set up Ctrl+C handler
     let running = grabber.get_running_flag();
     ctrlc::set_handler(move || {
         println!("\n🛑 Received Ctrl+C, shutting down gracefully...");
         running.store(false, std::sync::atomic::Ordering::SeqCst);
+        // Dump retained diagnostics so a live run's parse errors are not lost.
+        log_buffer::flush();
     })?;
-    
+
     if let Err(e) = grabber.start() {
         eprintln!("Failed to start frame capture: {}", e);
         return Err(format!("Frame capture failed: {}", e).into());
     }
-    
+
     println!("✅ Frame capture completed");
     Ok(())
 }
 
+/// Replay a recorded session of .dsql files back through the pipeline
+fn run_replay(
+    source_dir: String,
+    target_addr: Option<String>,
+    target_port: u16,
+    fps: f64,
+    loop_playback: bool,
+    decode_mode: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let target = match target_addr {
+        Some(addr) => ReplayTarget::Udp { addr, port: target_port },
+        None => ReplayTarget::LiveOutput { decode_mode },
+    };
+
+    let mut replayer = SimpleFrameReplayer::new(source_dir, target, fps, loop_playback);
+
+    // Set up Ctrl+C handler
+    let running = replayer.get_running_flag();
+    ctrlc::set_handler(move || {
+        running.store(false, std::sync::atomic::Ordering::SeqCst);
+    })?;
+
+    if let Err(e) = replayer.start() {
+        eprintln!("Failed to replay session: {}", e);
+        return Err(format!("Replay failed: {}", e).into());
+    }
+
+    Ok(())
+}
+
 /// Run frame capture from configuration file
 fn run_capture_from_config(config_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     let config = AppConfig::load_from_file(&config_path)?;
-    
+
     println!("🚀 Starting frame capture from config: {}", config_path.display());
-    
+
+    let fg = &config.framegrabber;
+    let control_addr = fg.control_addr.clone();
     let mut grabber = SimpleFrameGrabber::new(
-        config.framegrabber.udp_bind_addr,
-        config.framegrabber.udp_port,
-        config.framegrabber.output_directory,
-        config.framegrabber.enable_storage,
-        !config.framegrabber.enable_storage, // live_output is inverse of storage
-        false, // debug_mode - not supported in config yet
-        false, // decode_mode - not supported in config yet
+        fg.udp_bind_addr.clone(),
+        fg.udp_port,
+        fg.output_directory.clone(),
+        fg.enable_storage,
+        !fg.enable_storage, // live_output is inverse of storage
+        fg.debug,
+        fg.decode,
     );
-    
-    // Set up Ctrl+C handler
+
+    grabber.set_compression(fg.compression.clone());
+    grabber.set_codec(fg.frame_codec, fg.codec_prepass);
+    grabber.set_pixel_count_layout(fg.pixel_count_layout);
+    grabber.set_header_layout(fg.header_layout);
+    grabber.set_sync_limits(
+        fg.max_frame_size_hwords,
+        std::time::Duration::from_millis(fg.frame_timeout_ms),
+    );
+    grabber.set_core_affinity(capture::CoreAffinity {
+        receiver_core: fg.receiver_core,
+        writer_core: fg.writer_core,
+    });
+    grabber.set_output_fps(fg.output_fps);
+    grabber.set_decimation(fg.output_decimation);
+    grabber.set_transform_chain(shared::TransformChain::from_specs(&fg.transforms));
+
+    // Ctrl+C handler and, optionally, the runtime config control channel share
+    // the capture's running flag so both shut down together.
     let running = grabber.get_running_flag();
+
+    if let Some(addr) = control_addr {
+        let store = config_store::ConfigStore::new(config.clone(), config_path.clone());
+        let control_running = std::sync::Arc::clone(&running);
+        std::thread::spawn(move || {
+            if let Err(e) = store.serve_tcp(&addr, control_running) {
+                eprintln!("Config control channel failed: {e}");
+            }
+        });
+    }
+
     ctrlc::set_handler(move || {
         println!("\n🛑 Received Ctrl+C, shutting down gracefully...");
         running.store(false, std::sync::atomic::Ordering::SeqCst);
+        log_buffer::flush();
     })?;
-    
+
     if let Err(e) = grabber.start() {
         eprintln!("Failed to start frame capture: {}", e);
         return Err(format!("Frame capture failed: {}", e).into());
     }
-    
+
     println!("✅ Frame capture completed");
     Ok(())
 }
 
+/// Open a captured .dsql file in the interactive HWORD inspector
+fn run_inspect(file: String) -> Result<(), Box<dyn std::error::Error>> {
+    let mut inspector = inspect::Inspector::open(&file)
+        .map_err(|e| format!("Failed to open {file}: {e}"))?;
+    inspector.run()?;
+    Ok(())
+}
+
 /// Run the GUI application
 fn run_gui(config_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     println!("🖥️ Starting Frame Grabber GUI");
@@ -0,0 +1,268 @@
+/*!
+Pluggable destinations for completed live frames.
+
+`output_live_frame` used to write straight to stdout. That single stream can
+feed exactly one consumer and copies every frame through a pipe. The
+[`OutputSink`] trait abstracts the destination so the writer thread can fan
+frames out to other transports — notably [`SharedMemorySink`], which publishes
+recent frames into a named, memory-mapped ring that any number of local
+processes can `mmap` and read without a copy.
+*/
+
+use std::io::{self, Write};
+use std::sync::Arc;
+use shared::transform::TransformChain;
+use crate::capture::SimpleFrameGrabber;
+
+/// A destination for completed frames produced by the writer thread.
+pub trait OutputSink: Send {
+    /// Publish a completed frame. `frame` is the raw HWORD buffer; `decode_mode`
+    /// selects decoded-coordinate output where the sink supports it.
+    fn write_frame(
+        &mut self,
+        frame_counter: u32,
+        frame: &[u8],
+        decode_mode: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Selects which [`OutputSink`] the grabber builds for live output.
+#[derive(Debug, Clone)]
+pub enum OutputSinkConfig {
+    /// The classic stdout stream (`u32` size prefix + bytes, or decoded JSON).
+    Stdout,
+    /// A named shared-memory ring of recent frames for local consumers.
+    SharedMemory {
+        /// POSIX shared-memory object name, e.g. `/framegrabber`.
+        name: String,
+        /// Number of frame slots in the ring.
+        slot_count: usize,
+        /// Maximum frame size, in bytes, each slot can hold.
+        slot_size: usize,
+    },
+}
+
+impl OutputSinkConfig {
+    /// Default shared-memory geometry: 8 slots of 1 MiB.
+    pub const DEFAULT_SLOT_COUNT: usize = 8;
+    pub const DEFAULT_SLOT_SIZE: usize = 1024 * 1024;
+
+    /// Build the boxed sink this config describes.
+    pub fn build(
+        &self,
+        transform_chain: Arc<TransformChain>,
+        decimation: usize,
+    ) -> Result<Box<dyn OutputSink>, Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            OutputSinkConfig::Stdout => Ok(Box::new(StdoutSink { transform_chain, decimation })),
+            OutputSinkConfig::SharedMemory { name, slot_count, slot_size } => {
+                Ok(Box::new(SharedMemorySink::create(name, *slot_count, *slot_size)?))
+            }
+        }
+    }
+}
+
+/// Writes frames to stdout, preserving the historical wire format.
+pub struct StdoutSink {
+    transform_chain: Arc<TransformChain>,
+    decimation: usize,
+}
+
+impl OutputSink for StdoutSink {
+    fn write_frame(
+        &mut self,
+        frame_counter: u32,
+        frame: &[u8],
+        decode_mode: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let hwords_in_frame = frame.len() / 12;
+        SimpleFrameGrabber::output_live_frame(
+            frame,
+            frame_counter,
+            hwords_in_frame,
+            decode_mode,
+            &self.transform_chain,
+            self.decimation,
+        )
+    }
+}
+
+/// Fixed header at the start of the shared-memory region.
+///
+/// Laid out `#[repr(C)]` so an independently built consumer can map the same
+/// struct. `write_index` is the running count of published frames; the latest
+/// frame lives in slot `(write_index - 1) % slot_count`. A reader snapshots
+/// `write_index`, reads the slot, then re-checks `write_index` to detect a
+/// concurrent overwrite.
+#[repr(C)]
+struct ShmHeader {
+    magic: u32,
+    version: u32,
+    slot_count: u32,
+    slot_size: u32,
+    write_index: core::sync::atomic::AtomicU64,
+}
+
+/// Per-slot metadata entry, following the header in the mapping.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SlotMeta {
+    frame_number: u32,
+    frame_len: u32,
+}
+
+const SHM_MAGIC: u32 = 0x4653_474d; // "FSGM"
+const SHM_VERSION: u32 = 1;
+
+/// Publishes recent frames into a named POSIX shared-memory ring.
+pub struct SharedMemorySink {
+    name: std::ffi::CString,
+    base: *mut u8,
+    total_size: usize,
+    slot_count: usize,
+    slot_size: usize,
+}
+
+// The mapping is owned exclusively by this sink (the writer); the raw pointer is
+// only dereferenced from the single writer thread that holds the sink.
+unsafe impl Send for SharedMemorySink {}
+
+impl SharedMemorySink {
+    /// Create (or truncate) the shared-memory object and map it.
+    pub fn create(
+        name: &str,
+        slot_count: usize,
+        slot_size: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        use std::os::raw::c_int;
+
+        let slot_count = slot_count.max(1);
+        let slot_size = slot_size.max(12);
+        let header = core::mem::size_of::<ShmHeader>();
+        let meta = core::mem::size_of::<SlotMeta>() * slot_count;
+        let total_size = header + meta + slot_count * slot_size;
+
+        let cname = std::ffi::CString::new(name)?;
+
+        // SAFETY: FFI into the POSIX shared-memory API; arguments are validated
+        // by the kernel and we check every return value.
+        let fd: c_int = unsafe {
+            libc::shm_open(
+                cname.as_ptr(),
+                libc::O_CREAT | libc::O_RDWR,
+                0o600 as libc::mode_t,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        let ftrunc = unsafe { libc::ftruncate(fd, total_size as libc::off_t) };
+        if ftrunc < 0 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(fd) };
+            return Err(err.into());
+        }
+
+        let base = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                total_size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd,
+                0,
+            )
+        };
+        // The mapping keeps its own reference to the object; the fd is no longer
+        // needed once mapped.
+        unsafe { libc::close(fd) };
+        if base == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error().into());
+        }
+        let base = base as *mut u8;
+
+        // Initialize the header and zero the metadata array.
+        // SAFETY: `base` points at `total_size` writable bytes just mapped.
+        unsafe {
+            let hdr = base as *mut ShmHeader;
+            core::ptr::write(
+                hdr,
+                ShmHeader {
+                    magic: SHM_MAGIC,
+                    version: SHM_VERSION,
+                    slot_count: slot_count as u32,
+                    slot_size: slot_size as u32,
+                    write_index: core::sync::atomic::AtomicU64::new(0),
+                },
+            );
+            let meta_ptr = base.add(header) as *mut SlotMeta;
+            for i in 0..slot_count {
+                core::ptr::write(meta_ptr.add(i), SlotMeta { frame_number: 0, frame_len: 0 });
+            }
+        }
+
+        tracing::info!(
+            "🧠 Shared-memory sink ready: {} ({} slots × {} bytes)",
+            name, slot_count, slot_size
+        );
+
+        Ok(Self { name: cname, base, total_size, slot_count, slot_size })
+    }
+
+    fn header(&self) -> &ShmHeader {
+        // SAFETY: `base` is a live mapping with a `ShmHeader` at offset 0.
+        unsafe { &*(self.base as *const ShmHeader) }
+    }
+
+    fn data_offset(&self) -> usize {
+        core::mem::size_of::<ShmHeader>() + core::mem::size_of::<SlotMeta>() * self.slot_count
+    }
+}
+
+impl OutputSink for SharedMemorySink {
+    fn write_frame(
+        &mut self,
+        frame_counter: u32,
+        frame: &[u8],
+        _decode_mode: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use core::sync::atomic::Ordering;
+
+        let index = self.header().write_index.load(Ordering::Relaxed);
+        let slot = (index as usize) % self.slot_count;
+        let len = frame.len().min(self.slot_size);
+
+        let header_bytes = core::mem::size_of::<ShmHeader>();
+        let data_base = self.data_offset();
+
+        // SAFETY: all offsets stay inside the `total_size`-byte mapping, and this
+        // sink is the sole writer. We publish by storing the incremented
+        // `write_index` with Release ordering *after* the slot data and metadata
+        // are written, so a reader that observes the new index sees a complete
+        // frame.
+        unsafe {
+            let meta_ptr = self.base.add(header_bytes) as *mut SlotMeta;
+            let slot_ptr = self.base.add(data_base + slot * self.slot_size);
+            core::ptr::copy_nonoverlapping(frame.as_ptr(), slot_ptr, len);
+            core::ptr::write(
+                meta_ptr.add(slot),
+                SlotMeta { frame_number: frame_counter, frame_len: len as u32 },
+            );
+        }
+        self.header().write_index.store(index + 1, Ordering::Release);
+        debug_assert!(self.total_size >= data_base + self.slot_count * self.slot_size);
+        Ok(())
+    }
+}
+
+impl Drop for SharedMemorySink {
+    fn drop(&mut self) {
+        // SAFETY: unmap the region we mapped, then unlink the named object so it
+        // does not leak across runs.
+        unsafe {
+            libc::munmap(self.base as *mut libc::c_void, self.total_size);
+            libc::shm_unlink(self.name.as_ptr());
+        }
+    }
+}
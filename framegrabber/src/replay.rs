@@ -0,0 +1,192 @@
+/*!
+Replay of recorded capture sessions.
+
+Where [`SimpleFrameGrabber`](crate::capture::SimpleFrameGrabber) turns a live UDP
+stream into `NNNNNNNN.dsql` files, [`SimpleFrameReplayer`] does the inverse: it
+reads a session directory back in frame-number order and re-emits the frames,
+either as UDP datagrams to a target address (feeding an unmodified grabber) or
+straight into the live/decode output path without a network round-trip. This
+makes the decode pipeline and downstream tools testable against recorded
+captures, with no live hardware in the loop.
+*/
+
+use std::net::UdpSocket;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn, error};
+use shared::transform::TransformChain;
+use crate::capture::{parse_dsql_frame_number, SimpleFrameGrabber};
+
+/// Number of HWORDs carried per replayed UDP datagram.
+///
+/// Matches the datagram granularity the grabber expects; frames are sliced on
+/// HWORD boundaries so the receiver's count-based synchronization still lines up.
+const HWORDS_PER_DATAGRAM: usize = 120;
+
+/// Where a [`SimpleFrameReplayer`] sends the frames it reads back.
+pub enum ReplayTarget {
+    /// Re-serialize frames as UDP datagrams to `addr:port`, feeding a grabber.
+    Udp { addr: String, port: u16 },
+    /// Push frames directly through the live/decode output path, skipping the
+    /// network. `decode_mode` selects JSON coordinates vs. raw binary framing.
+    LiveOutput { decode_mode: bool },
+}
+
+/// Reads a recorded session directory and re-emits its frames.
+pub struct SimpleFrameReplayer {
+    source_dir: String,
+    target: ReplayTarget,
+    /// Playback rate in frames per second (`0.0` replays as fast as possible).
+    fps: f64,
+    /// Replay the session on a loop until the running flag is cleared.
+    loop_playback: bool,
+    /// Transform pipeline applied to decoded points in `LiveOutput` decode mode.
+    transform_chain: Arc<TransformChain>,
+    /// Point decimation factor applied when decoding in `LiveOutput` decode mode.
+    decimation: usize,
+    running: Arc<AtomicBool>,
+}
+
+impl SimpleFrameReplayer {
+    /// Create a replayer for the session at `source_dir`.
+    pub fn new(source_dir: String, target: ReplayTarget, fps: f64, loop_playback: bool) -> Self {
+        Self {
+            source_dir,
+            target,
+            fps,
+            loop_playback,
+            transform_chain: Arc::new(TransformChain::new()),
+            decimation: 1,
+            running: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Install the coordinate transform pipeline applied in decode mode.
+    pub fn set_transform_chain(&mut self, chain: TransformChain) {
+        self.transform_chain = Arc::new(chain);
+    }
+
+    /// Set the point decimation factor applied when decoding in decode mode.
+    pub fn set_decimation(&mut self, factor: usize) {
+        self.decimation = factor.max(1);
+    }
+
+    /// Get a reference to the running flag for external control.
+    pub fn get_running_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.running)
+    }
+
+    /// Discover the session's `.dsql` files, sorted by frame number.
+    fn discover_frames(dir: &str) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut frames: Vec<(u32, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            if !entry.metadata().map(|m| m.is_file()).unwrap_or(false) {
+                continue;
+            }
+            let name = entry.file_name();
+            if let Some(num) = name.to_str().and_then(parse_dsql_frame_number) {
+                frames.push((num, entry.path()));
+            }
+        }
+        frames.sort_by_key(|(num, _)| *num);
+        Ok(frames.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Start replaying. Blocks until the whole session has been emitted (or, with
+    /// `loop_playback`, until the running flag is cleared).
+    pub fn start(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let frames = Self::discover_frames(&self.source_dir)?;
+        if frames.is_empty() {
+            warn!("No .dsql frames found in {}", self.source_dir);
+            return Ok(());
+        }
+        info!("▶️  Replaying {} frames from {}", frames.len(), self.source_dir);
+
+        let frame_interval = if self.fps > 0.0 {
+            Some(Duration::from_secs_f64(1.0 / self.fps))
+        } else {
+            None
+        };
+
+        // A UDP target needs a single socket held open across the whole session.
+        let socket = match &self.target {
+            ReplayTarget::Udp { .. } => Some(UdpSocket::bind("0.0.0.0:0")?),
+            ReplayTarget::LiveOutput { .. } => None,
+        };
+
+        let mut frame_counter = 0u32;
+        loop {
+            for path in &frames {
+                if !self.running.load(Ordering::SeqCst) {
+                    info!("Replay stopped by running flag");
+                    return Ok(());
+                }
+
+                let raw = match std::fs::read(path) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        error!("Failed to read frame {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                // Transparently decode stored records (compressed or raw);
+                // legacy raw captures pass through unchanged.
+                let data = match crate::codec::decode(&raw) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        error!("Failed to decode frame {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                self.emit_frame(&data, frame_counter, socket.as_ref())?;
+                frame_counter = frame_counter.wrapping_add(1);
+
+                if let Some(interval) = frame_interval {
+                    std::thread::sleep(interval);
+                }
+            }
+
+            if !self.loop_playback {
+                break;
+            }
+        }
+
+        info!("✅ Replay finished ({} frames emitted)", frame_counter);
+        Ok(())
+    }
+
+    /// Emit a single frame to the configured target.
+    fn emit_frame(
+        &self,
+        data: &[u8],
+        frame_counter: u32,
+        socket: Option<&UdpSocket>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match &self.target {
+            ReplayTarget::Udp { addr, port } => {
+                let socket = socket.expect("UDP target always has a socket");
+                let dest = format!("{}:{}", addr, port);
+                let datagram = HWORDS_PER_DATAGRAM * 12;
+                for chunk in data.chunks(datagram) {
+                    socket.send_to(chunk, &dest)?;
+                }
+            }
+            ReplayTarget::LiveOutput { decode_mode } => {
+                let hwords_in_frame = data.len() / 12;
+                SimpleFrameGrabber::output_live_frame(
+                    data,
+                    frame_counter,
+                    hwords_in_frame,
+                    *decode_mode,
+                    &self.transform_chain,
+                    self.decimation,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+/*!
+Preallocated, contiguous ring-buffer transport shared between the UDP receiver
+and the file-writer thread.
+
+The original pipeline shipped every datagram as a freshly allocated `Vec<u8>`
+through a bounded crossbeam channel and dropped packets outright when the writer
+fell behind. This module replaces that with a single up-front allocation of
+fixed-size "gulp" slots: the receiver fills the next free slot in place (the UDP
+socket `recv`s directly into a `&mut [u8]` borrow, no per-packet allocation) and
+advances a write cursor, while the writer consumes whole slots behind a read
+cursor. The two cursors are atomics with acquire/release ordering so the writer
+never observes a slot the receiver is still filling.
+
+It is a single-producer / single-consumer structure: exactly one receiver thread
+and one writer thread. On a full ring the producer gets explicit backpressure
+(`reserve` returns `None` and bumps the overrun counter) rather than silently
+dropping, so callers can block or account for the stall.
+*/
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// A fixed-capacity SPSC ring of byte slots.
+pub struct GulpRing {
+    /// Backing storage: `slot_count * slot_size` bytes.
+    storage: UnsafeCell<Box<[u8]>>,
+    /// Valid byte length written into each slot.
+    lengths: UnsafeCell<Box<[usize]>>,
+    slot_size: usize,
+    slot_count: usize,
+    /// Next slot the producer will fill (monotonic, wraps via modulo).
+    write_cursor: AtomicUsize,
+    /// Next slot the consumer will read (monotonic, wraps via modulo).
+    read_cursor: AtomicUsize,
+    /// Count of `reserve` calls refused because the ring was full.
+    overruns: AtomicU64,
+}
+
+// SAFETY: access is disciplined by the SPSC contract — the producer only
+// touches the slot at `write_cursor` before publishing it, and the consumer
+// only touches slots strictly behind `write_cursor`. The atomic cursors with
+// acquire/release ordering establish the happens-before edge between them.
+unsafe impl Sync for GulpRing {}
+unsafe impl Send for GulpRing {}
+
+impl GulpRing {
+    /// Allocate a ring of `slot_count` slots, each `slot_size` bytes.
+    pub fn new(slot_count: usize, slot_size: usize) -> Self {
+        assert!(slot_count > 0 && slot_size > 0, "ring dimensions must be non-zero");
+        Self {
+            storage: UnsafeCell::new(vec![0u8; slot_count * slot_size].into_boxed_slice()),
+            lengths: UnsafeCell::new(vec![0usize; slot_count].into_boxed_slice()),
+            slot_size,
+            slot_count,
+            write_cursor: AtomicUsize::new(0),
+            read_cursor: AtomicUsize::new(0),
+            overruns: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum bytes that fit in one slot.
+    pub fn slot_size(&self) -> usize {
+        self.slot_size
+    }
+
+    /// Number of times the ring was full when a producer tried to reserve.
+    pub fn overruns(&self) -> u64 {
+        self.overruns.load(Ordering::Relaxed)
+    }
+
+    /// Reserve the next free slot for the producer, or `None` under backpressure.
+    ///
+    /// The returned [`Reservation`] hands out a `&mut [u8]` for the UDP socket to
+    /// `recv` into; call [`Reservation::commit`] with the number of bytes
+    /// received to publish it to the consumer.
+    pub fn reserve(&self) -> Option<Reservation<'_>> {
+        let write = self.write_cursor.load(Ordering::Relaxed);
+        let read = self.read_cursor.load(Ordering::Acquire);
+        if write - read >= self.slot_count {
+            self.overruns.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let slot = write % self.slot_count;
+        let start = slot * self.slot_size;
+        // SAFETY: this slot is ahead of the read cursor and not yet published,
+        // so the consumer cannot be looking at it.
+        let buf = unsafe {
+            let storage = &mut *self.storage.get();
+            &mut storage[start..start + self.slot_size]
+        };
+        Some(Reservation { ring: self, slot, write, buf })
+    }
+
+    /// Read the next ready slot with `f`, advancing the read cursor afterwards.
+    ///
+    /// Returns `None` when the ring is empty.
+    pub fn read_with<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let read = self.read_cursor.load(Ordering::Relaxed);
+        let write = self.write_cursor.load(Ordering::Acquire);
+        if read == write {
+            return None;
+        }
+        let slot = read % self.slot_count;
+        let start = slot * self.slot_size;
+        // SAFETY: the slot is strictly behind the write cursor, so the producer
+        // has finished filling it and published via the release store below.
+        let bytes = unsafe {
+            let len = (*self.lengths.get())[slot];
+            let storage = &*self.storage.get();
+            &storage[start..start + len]
+        };
+        let result = f(bytes);
+        self.read_cursor.store(read + 1, Ordering::Release);
+        Some(result)
+    }
+
+    /// Whether any slot is ready to be consumed.
+    pub fn has_data(&self) -> bool {
+        self.read_cursor.load(Ordering::Relaxed) != self.write_cursor.load(Ordering::Acquire)
+    }
+}
+
+/// A borrowed, in-place slot the producer fills before publishing.
+pub struct Reservation<'a> {
+    ring: &'a GulpRing,
+    slot: usize,
+    write: usize,
+    buf: &'a mut [u8],
+}
+
+impl<'a> Reservation<'a> {
+    /// Mutable view of the slot for the socket to receive into.
+    pub fn buf(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    /// Publish `len` received bytes to the consumer, advancing the write cursor.
+    pub fn commit(self, len: usize) {
+        debug_assert!(len <= self.ring.slot_size);
+        // SAFETY: the consumer only reads this length after observing the
+        // release store to `write_cursor` below.
+        unsafe {
+            (*self.ring.lengths.get())[self.slot] = len;
+        }
+        self.ring.write_cursor.store(self.write + 1, Ordering::Release);
+    }
+}
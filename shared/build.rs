@@ -0,0 +1,182 @@
+//! Build script generating the HWORD control-bit and field-layout tables from
+//! the declarative `protocol.spec`.
+//!
+//! Modeled on the build-time table generation used by projects that keep an
+//! instruction/opcode table in a sidecar file: the single source of truth is
+//! `protocol.spec`, and the `ControlBits` enum, its `from_u8`/classifier
+//! methods, the shift/mask constants consumed by `HWord::from_bytes`/`to_bytes`,
+//! and the `PROTOCOL_VERSION` tag are all emitted into `$OUT_DIR/protocol.rs`.
+//! `hword.rs` then `include!`s that file. Pointing the crate at a different
+//! firmware revision is a spec edit, not a hand-audit of bit math.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// One `control` enumerant parsed out of the spec.
+struct Control {
+    name: String,
+    value: u8,
+    header: bool,
+    pixel: bool,
+    idle: bool,
+    frame_start: bool,
+}
+
+fn main() {
+    let spec_path = "protocol.spec";
+    println!("cargo:rerun-if-changed={spec_path}");
+
+    let spec = fs::read_to_string(spec_path)
+        .unwrap_or_else(|e| panic!("failed to read {spec_path}: {e}"));
+
+    let mut version: u16 = 0;
+    let mut word_bits: u32 = 0;
+    let mut control_bits: u32 = 0;
+    let mut parity_bit: u32 = 0;
+    let mut data_field_bits: u32 = 0;
+    let mut controls: Vec<Control> = Vec::new();
+
+    for (lineno, raw) in spec.lines().enumerate() {
+        let line = raw.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let directive = tokens.next().unwrap();
+        match directive {
+            "version" => version = parse_u(next(&mut tokens, lineno)) as u16,
+            "word_bits" => word_bits = parse_u(next(&mut tokens, lineno)) as u32,
+            "control_bits" => control_bits = parse_u(next(&mut tokens, lineno)) as u32,
+            "parity_bit" => parity_bit = parse_u(next(&mut tokens, lineno)) as u32,
+            "data_field_bits" => data_field_bits = parse_u(next(&mut tokens, lineno)) as u32,
+            "control" => {
+                let name = next(&mut tokens, lineno).to_string();
+                let eq = next(&mut tokens, lineno);
+                assert_eq!(eq, "=", "expected '=' in control on line {}", lineno + 1);
+                let value = parse_u(next(&mut tokens, lineno)) as u8;
+                let mut control = Control {
+                    name,
+                    value,
+                    header: false,
+                    pixel: false,
+                    idle: false,
+                    frame_start: false,
+                };
+                for flag in tokens {
+                    match flag {
+                        "header" => control.header = true,
+                        "pixel" => control.pixel = true,
+                        "idle" => control.idle = true,
+                        "frame_start" => control.frame_start = true,
+                        other => panic!("unknown control flag '{other}' on line {}", lineno + 1),
+                    }
+                }
+                controls.push(control);
+            }
+            other => panic!("unknown directive '{other}' on line {}", lineno + 1),
+        }
+    }
+
+    assert!(word_bits > 0 && control_bits > 0, "protocol.spec missing word/control widths");
+    assert_eq!(
+        controls.len(),
+        1 << control_bits,
+        "protocol.spec must enumerate all {} control values",
+        1 << control_bits
+    );
+
+    let control_shift = word_bits - control_bits;
+    let control_mask = (1u8 << control_bits) - 1;
+
+    let mut out = String::new();
+    out.push_str("// @generated by build.rs from protocol.spec — do not edit by hand.\n\n");
+
+    writeln!(out, "/// Protocol revision tag carried into parsing.").unwrap();
+    writeln!(out, "pub const PROTOCOL_VERSION: u16 = {version};\n").unwrap();
+
+    writeln!(out, "/// Total bits in an HWORD.").unwrap();
+    writeln!(out, "pub const WORD_BITS: u32 = {word_bits};").unwrap();
+    writeln!(out, "/// Bit shift of the control-bit field within the word.").unwrap();
+    writeln!(out, "pub(crate) const CONTROL_SHIFT: u32 = {control_shift};").unwrap();
+    writeln!(out, "/// Mask selecting the raw control-bit value.").unwrap();
+    writeln!(out, "pub(crate) const CONTROL_MASK: u8 = 0b{control_mask:b};").unwrap();
+    writeln!(out, "/// Bit position of the parity bit.").unwrap();
+    writeln!(out, "pub(crate) const PARITY_SHIFT: u32 = {parity_bit};").unwrap();
+    writeln!(out, "/// Width of the payload data field.").unwrap();
+    writeln!(out, "pub const DATA_FIELD_BITS: u32 = {data_field_bits};").unwrap();
+    writeln!(out, "/// Mask selecting the payload data field.").unwrap();
+    writeln!(out, "pub(crate) const DATA_FIELD_MASK: u128 = (1u128 << {data_field_bits}) - 1;\n").unwrap();
+
+    // The enum itself.
+    out.push_str("/// Control bit values for HWORDs according to the protocol specification\n");
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+    out.push_str("#[repr(u8)]\n");
+    out.push_str("pub enum ControlBits {\n");
+    for c in &controls {
+        writeln!(out, "    {} = 0b{:03b},", c.name, c.value).unwrap();
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("impl ControlBits {\n");
+
+    // from_u8
+    out.push_str("    /// Parse control bits from a u8 value\n");
+    out.push_str("    pub fn from_u8(value: u8) -> Option<Self> {\n");
+    out.push_str("        match value & CONTROL_MASK {\n");
+    for c in &controls {
+        writeln!(out, "            0b{:03b} => Some(Self::{}),", c.value, c.name).unwrap();
+    }
+    out.push_str("            _ => None,\n");
+    out.push_str("        }\n    }\n\n");
+
+    classifier(&mut out, "is_header", "a header", &controls, |c| c.header);
+    classifier(&mut out, "is_pixel", "a pixel", &controls, |c| c.pixel);
+    classifier(&mut out, "is_frame_start", "a frame start", &controls, |c| c.frame_start);
+    classifier(&mut out, "is_idle", "an idle", &controls, |c| c.idle);
+
+    out.push_str("}\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest = Path::new(&out_dir).join("protocol.rs");
+    fs::write(&dest, out).unwrap_or_else(|e| panic!("failed to write {}: {e}", dest.display()));
+}
+
+/// Emit a `matches!`-based classifier method for the controls passing `pred`.
+fn classifier(
+    out: &mut String,
+    method: &str,
+    description: &str,
+    controls: &[Control],
+    pred: impl Fn(&Control) -> bool,
+) {
+    let arms: Vec<String> = controls
+        .iter()
+        .filter(|c| pred(c))
+        .map(|c| format!("Self::{}", c.name))
+        .collect();
+    writeln!(out, "    /// Check if this is {description} HWORD").unwrap();
+    writeln!(out, "    pub fn {method}(self) -> bool {{").unwrap();
+    if arms.is_empty() {
+        out.push_str("        false\n");
+    } else {
+        writeln!(out, "        matches!(self, {})", arms.join(" | ")).unwrap();
+    }
+    out.push_str("    }\n\n");
+}
+
+fn next<'a>(tokens: &mut impl Iterator<Item = &'a str>, lineno: usize) -> &'a str {
+    tokens
+        .next()
+        .unwrap_or_else(|| panic!("unexpected end of line {}", lineno + 1))
+}
+
+/// Parse a decimal or `0b`-prefixed binary literal.
+fn parse_u(token: &str) -> u64 {
+    if let Some(bits) = token.strip_prefix("0b") {
+        u64::from_str_radix(bits, 2).unwrap_or_else(|e| panic!("bad binary literal '{token}': {e}"))
+    } else {
+        token.parse().unwrap_or_else(|e| panic!("bad integer literal '{token}': {e}"))
+    }
+}
@@ -0,0 +1,106 @@
+/*!
+Zero-copy Apache Arrow export for decoded coordinate data.
+
+The NumPy path in the `dsql_decoder` extension builds an all-`f8` structured
+array, copying every column into NumPy and widening the non-float ones first.
+[`coordinate_data_to_arrow`] instead builds one typed Arrow array per requested
+[`FieldType`] — `Float64` for x/y/z, `UInt16` for intensity, `Boolean` for
+gain/over_range — and packs them into a [`RecordBatch`]. The batch can then be
+handed to `pyarrow`/Polars over the Arrow C Data Interface without another copy,
+so downstream users get columnar analytics on decoded frames directly.
+*/
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use arrow::array::{
+    ArrayRef, BooleanBuilder, Float64Builder, UInt16Builder,
+};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+use crate::coordinates::{CoordinateData, FieldType, FieldWhitelist};
+
+/// Build an Arrow [`RecordBatch`] with one column per whitelisted field.
+///
+/// Columns are emitted in [`FieldType`] order (x, y, z, intensity, gain,
+/// over_range), each using the natural Arrow type for the field. Missing
+/// per-point values become Arrow nulls, matching the `Option` semantics of
+/// [`CoordinatePoint`](crate::coordinates::CoordinatePoint).
+pub fn coordinate_data_to_arrow(
+    data: &CoordinateData,
+    whitelist: &FieldWhitelist,
+) -> Result<RecordBatch, ArrowError> {
+    let n = data.len();
+    let mut fields: Vec<Field> = Vec::new();
+    let mut columns: Vec<ArrayRef> = Vec::new();
+
+    // Emit columns in a stable order so the schema is deterministic.
+    const ORDER: [FieldType; 6] = [
+        FieldType::X,
+        FieldType::Y,
+        FieldType::Z,
+        FieldType::Intensity,
+        FieldType::Gain,
+        FieldType::OverRange,
+    ];
+
+    for field in ORDER {
+        if !whitelist.includes(&field) {
+            continue;
+        }
+        let (arrow_field, column) = match field {
+            FieldType::X | FieldType::Y | FieldType::Z => {
+                let column = match field {
+                    FieldType::X => data.x.as_ref(),
+                    FieldType::Y => data.y.as_ref(),
+                    _ => data.z.as_ref(),
+                };
+                let mut builder = Float64Builder::with_capacity(n);
+                if let Some(column) = column {
+                    for i in 0..n {
+                        builder.append_option(column.get(i));
+                    }
+                }
+                (
+                    Field::new(field.as_str(), DataType::Float64, true),
+                    Arc::new(builder.finish()) as ArrayRef,
+                )
+            }
+            FieldType::Intensity => {
+                let mut builder = UInt16Builder::with_capacity(n);
+                if let Some(column) = data.intensity.as_ref() {
+                    for i in 0..n {
+                        builder.append_option(column.get(i));
+                    }
+                }
+                (
+                    Field::new(field.as_str(), DataType::UInt16, true),
+                    Arc::new(builder.finish()) as ArrayRef,
+                )
+            }
+            FieldType::Gain | FieldType::OverRange => {
+                let column = if field == FieldType::Gain {
+                    data.gain.as_ref()
+                } else {
+                    data.over_range.as_ref()
+                };
+                let mut builder = BooleanBuilder::with_capacity(n);
+                if let Some(column) = column {
+                    for i in 0..n {
+                        builder.append_option(column.get(i));
+                    }
+                }
+                (
+                    Field::new(field.as_str(), DataType::Boolean, true),
+                    Arc::new(builder.finish()) as ArrayRef,
+                )
+            }
+        };
+        fields.push(arrow_field);
+        columns.push(column);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
@@ -0,0 +1,140 @@
+/*!
+Injectable clock abstraction for deterministic timing.
+
+Capture and GUI timing used to read the wall clock directly, which makes frame
+timestamps irreproducible under test. Following the same pattern moonfire-nvr
+uses, [`Clocks`] is a small `Send + Sync + 'static` trait exposing a
+[`realtime`](Clocks::realtime) wall-clock reading and a
+[`monotonic`](Clocks::monotonic) reading that never goes backwards. Production
+code wires in [`SystemClocks`]; tests wire in [`SimulatedClock`], which advances
+by a fixed step on every reading so a run produces the same
+[`Timestamp`](crate::clock::Timestamp) sequence every time.
+
+A [`Frame`](crate::frame::Frame) is stamped with the clock's readings as it
+completes, so downstream code computes true frames-per-second from timestamp
+deltas instead of guessing from repaint intervals.
+*/
+
+#[cfg(feature = "alloc")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A paired monotonic and wall-clock reading taken when a frame completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Timestamp {
+    /// Nanoseconds from a monotonic, never-decreasing source. Safe for deltas.
+    pub monotonic_nanos: u64,
+    /// Nanoseconds since the Unix epoch, for human-facing timestamps.
+    pub realtime_nanos: u64,
+}
+
+impl Timestamp {
+    /// Monotonic nanoseconds elapsed since `earlier`, saturating at zero.
+    pub fn monotonic_since(&self, earlier: &Timestamp) -> u64 {
+        self.monotonic_nanos.saturating_sub(earlier.monotonic_nanos)
+    }
+}
+
+/// Source of monotonic and wall-clock readings.
+pub trait Clocks: Send + Sync + 'static {
+    /// Wall-clock time in nanoseconds since the Unix epoch.
+    fn realtime(&self) -> u64;
+
+    /// Monotonic time in nanoseconds from an arbitrary fixed origin.
+    fn monotonic(&self) -> u64;
+
+    /// Take a paired reading for stamping a completed frame.
+    fn now(&self) -> Timestamp {
+        Timestamp {
+            monotonic_nanos: self.monotonic(),
+            realtime_nanos: self.realtime(),
+        }
+    }
+}
+
+/// Real clock backed by the host's `SystemTime`/`Instant`.
+#[cfg(feature = "std")]
+pub struct SystemClocks {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl SystemClocks {
+    /// Create a clock whose monotonic origin is the moment of construction.
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clocks for SystemClocks {
+    fn realtime(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+
+    fn monotonic(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+}
+
+/// Deterministic clock that advances by a fixed step per reading.
+///
+/// Each call to [`monotonic`](Clocks::monotonic)/[`realtime`](Clocks::realtime)
+/// returns the current value and then advances it by `step_nanos`, so a test
+/// that stamps N frames sees evenly spaced, reproducible timestamps.
+#[cfg(feature = "alloc")]
+pub struct SimulatedClock {
+    step_nanos: u64,
+    monotonic: AtomicU64,
+    realtime: AtomicU64,
+}
+
+#[cfg(feature = "alloc")]
+impl SimulatedClock {
+    /// Create a clock starting at `start_nanos` that advances `step_nanos` per
+    /// reading on each of the two time bases.
+    pub fn new(start_nanos: u64, step_nanos: u64) -> Self {
+        Self {
+            step_nanos,
+            monotonic: AtomicU64::new(start_nanos),
+            realtime: AtomicU64::new(start_nanos),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Clocks for SimulatedClock {
+    fn realtime(&self) -> u64 {
+        self.realtime.fetch_add(self.step_nanos, Ordering::Relaxed)
+    }
+
+    fn monotonic(&self) -> u64 {
+        self.monotonic.fetch_add(self.step_nanos, Ordering::Relaxed)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_clock_advances_by_fixed_step() {
+        let clock = SimulatedClock::new(1_000, 250);
+        let first = clock.now();
+        let second = clock.now();
+        assert_eq!(first.monotonic_nanos, 1_000);
+        assert_eq!(second.monotonic_nanos, 1_250);
+        assert_eq!(second.monotonic_since(&first), 250);
+    }
+}
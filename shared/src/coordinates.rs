@@ -8,10 +8,15 @@ coordinates and other frame data fields.
 use crate::hword::{HWord, ControlBits};
 use crate::protocol::COORDINATE_SCALE_FACTOR;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use thiserror::Error;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, vec::Vec};
 
 /// Represents the fields that can be extracted from frame data
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+// `Ord` lets the whitelist use a `BTreeSet`, which is available under `alloc`
+// without pulling in `std`'s `HashSet`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum FieldType {
     X,
     Y,
@@ -24,14 +29,22 @@ pub enum FieldType {
 impl FieldType {
     /// Parse field type from string (case-insensitive)
     pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "x" => Some(Self::X),
-            "y" => Some(Self::Y),
-            "z" => Some(Self::Z),
-            "intensity" => Some(Self::Intensity),
-            "gain" => Some(Self::Gain),
-            "over_range" | "overrange" => Some(Self::OverRange),
-            _ => None,
+        // `eq_ignore_ascii_case` avoids allocating a lowercased `String`, so this
+        // stays usable on the heap-free path.
+        if s.eq_ignore_ascii_case("x") {
+            Some(Self::X)
+        } else if s.eq_ignore_ascii_case("y") {
+            Some(Self::Y)
+        } else if s.eq_ignore_ascii_case("z") {
+            Some(Self::Z)
+        } else if s.eq_ignore_ascii_case("intensity") {
+            Some(Self::Intensity)
+        } else if s.eq_ignore_ascii_case("gain") {
+            Some(Self::Gain)
+        } else if s.eq_ignore_ascii_case("over_range") || s.eq_ignore_ascii_case("overrange") {
+            Some(Self::OverRange)
+        } else {
+            None
         }
     }
     
@@ -46,14 +59,170 @@ impl FieldType {
             Self::OverRange => "over_range",
         }
     }
+
+    /// Stable index into a [`FieldLayout`]'s per-field table.
+    const fn index(self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::Y => 1,
+            Self::Z => 2,
+            Self::Intensity => 3,
+            Self::Gain => 4,
+            Self::OverRange => 5,
+        }
+    }
+}
+
+/// All field types in [`FieldType::index`] order.
+const FIELD_ORDER: [FieldType; 6] = [
+    FieldType::X,
+    FieldType::Y,
+    FieldType::Z,
+    FieldType::Intensity,
+    FieldType::Gain,
+    FieldType::OverRange,
+];
+
+/// Sign-extend the low `bits` of `raw` into a full `i32`.
+///
+/// Shifting up to the top of the word and back down with an arithmetic shift
+/// replicates the sign bit without per-field OR-masks — one correct routine for
+/// every field width. `bits` must be in `1..=32`.
+pub fn sign_extend(raw: u32, bits: u32) -> i32 {
+    ((raw << (32 - bits)) as i32) >> (32 - bits)
+}
+
+/// Bit-layout of a single decoded field within the 128-bit pixel word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    /// Bit offset of the field's least-significant bit.
+    pub offset: u32,
+    /// Field width in bits (`1..=32`).
+    pub width: u32,
+    /// Whether the raw value is sign-extended (coordinates) or zero-extended.
+    pub signed: bool,
+    /// Binary-point position: the decoded `f64` is `value / 2^fractional_bits`.
+    /// Integer/flag fields use `0`.
+    pub fractional_bits: u32,
+}
+
+impl FieldSpec {
+    /// One past the highest bit this field occupies.
+    const fn end(&self) -> u32 {
+        self.offset + self.width
+    }
+
+    /// Extract the field's raw, still-unextended value from the pixel word.
+    fn extract_raw(&self, data: u128) -> u32 {
+        let mask = (1u128 << self.width) - 1;
+        ((data >> self.offset) & mask) as u32
+    }
+
+    /// Extract and scale the field as an `f64`, honouring sign and binary point.
+    fn decode_f64(&self, data: u128) -> f64 {
+        let raw = self.extract_raw(data);
+        let value = if self.signed {
+            sign_extend(raw, self.width)
+        } else {
+            raw as i32
+        };
+        value as f64 / (1u64 << self.fractional_bits) as f64
+    }
+}
+
+/// Errors rejecting a misconfigured [`FieldLayout`] at construction time.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FieldLayoutError {
+    /// A field width is outside the representable `1..=32` range.
+    #[error("field {field} width {width} is out of the supported 1..=32 range")]
+    BadWidth { field: &'static str, width: u32 },
+    /// A field extends past bit 127 of the pixel word.
+    #[error("field {field} spans bits {offset}..{end}, past the 128-bit word")]
+    OutOfRange { field: &'static str, offset: u32, end: u32 },
+    /// Two fields claim overlapping bit ranges.
+    #[error("fields {a} and {b} occupy overlapping bits")]
+    Overlap { a: &'static str, b: &'static str },
+}
+
+/// Table describing where every [`FieldType`] lives in the 128-bit pixel word.
+///
+/// Supporting a new sensor format becomes a data change — build a different
+/// `FieldLayout` — rather than editing the extraction code. Construction
+/// validates that every width is representable, no field runs past bit 127, and
+/// no two fields overlap, so a bad layout is rejected up front instead of
+/// silently producing garbage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldLayout {
+    specs: [FieldSpec; 6],
+}
+
+impl FieldLayout {
+    /// Build and validate a layout from per-field specs, indexed by
+    /// [`FieldType::index`].
+    pub fn new(specs: [FieldSpec; 6]) -> core::result::Result<Self, FieldLayoutError> {
+        for field in FIELD_ORDER {
+            let spec = specs[field.index()];
+            if spec.width < 1 || spec.width > 32 {
+                return Err(FieldLayoutError::BadWidth { field: field.as_str(), width: spec.width });
+            }
+            if spec.end() > 128 {
+                return Err(FieldLayoutError::OutOfRange {
+                    field: field.as_str(),
+                    offset: spec.offset,
+                    end: spec.end(),
+                });
+            }
+        }
+        // Reject overlapping bit ranges (gaps between fields are fine).
+        for (i, a) in FIELD_ORDER.iter().enumerate() {
+            for b in &FIELD_ORDER[i + 1..] {
+                let sa = specs[a.index()];
+                let sb = specs[b.index()];
+                if sa.offset < sb.end() && sb.offset < sa.end() {
+                    return Err(FieldLayoutError::Overlap { a: a.as_str(), b: b.as_str() });
+                }
+            }
+        }
+        Ok(Self { specs })
+    }
+
+    /// The reference firmware layout: X/Y as signed Q9.10 (19 bits), Z as signed
+    /// Q12.10 (22 bits), a 12-bit intensity, and the over-range/gain flags.
+    pub fn standard() -> Self {
+        let q10 = |offset, width| FieldSpec { offset, width, signed: true, fractional_bits: 10 };
+        let flag = |offset| FieldSpec { offset, width: 1, signed: false, fractional_bits: 0 };
+        let specs = [
+            q10(0, 19),  // X
+            q10(24, 19), // Y
+            q10(48, 22), // Z
+            FieldSpec { offset: 72, width: 12, signed: false, fractional_bits: 0 }, // Intensity
+            flag(91),    // Gain (HG/LG)
+            flag(90),    // OverRange
+        ];
+        // The reference layout is known-good.
+        Self::new(specs).expect("reference FieldLayout is valid")
+    }
+
+    /// The [`FieldSpec`] for `field`.
+    pub fn spec(&self, field: FieldType) -> FieldSpec {
+        self.specs[field.index()]
+    }
+}
+
+impl Default for FieldLayout {
+    fn default() -> Self {
+        Self::standard()
+    }
 }
 
 /// Field whitelist for controlling which fields to extract
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
 pub struct FieldWhitelist {
-    fields: HashSet<FieldType>,
+    fields: BTreeSet<FieldType>,
 }
 
+#[cfg(feature = "alloc")]
 impl FieldWhitelist {
     /// Create a new field whitelist from a list of field names
     pub fn new(field_names: &[&str]) -> Self {
@@ -85,19 +254,86 @@ impl FieldWhitelist {
     }
     
     /// Get all included fields
-    pub fn fields(&self) -> &HashSet<FieldType> {
+    pub fn fields(&self) -> &BTreeSet<FieldType> {
         &self.fields
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for FieldWhitelist {
     fn default() -> Self {
         Self::all()
     }
 }
 
+/// A signed Q22.10 fixed-point value: an `i32` with 10 fractional bits.
+///
+/// The hardware scales coordinates by `2^10` (see
+/// [`COORDINATE_SCALE_FACTOR`](crate::protocol::COORDINATE_SCALE_FACTOR)), so a
+/// raw field value *is* a `Fixed` with the binary point 10 bits from the right.
+/// This type lets firmware on FPU-less targets do exact integer math that
+/// matches the hardware bit-for-bit, while host tooling keeps the `f64` path via
+/// [`to_f64`](Self::to_f64).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    /// Number of fractional bits in the Q22.10 representation.
+    pub const FRACTIONAL_BITS: u32 = 10;
+
+    /// Scaling factor applied by the fixed-point representation (`2^10`).
+    pub const SCALE: i32 = 1 << Self::FRACTIONAL_BITS;
+
+    /// Wrap a raw, already-sign-extended HWORD field value as a `Fixed`.
+    pub const fn from_raw(raw: i32) -> Self {
+        Fixed(raw)
+    }
+
+    /// Return the underlying raw integer (the HWORD coordinate field value).
+    pub const fn to_raw(self) -> i32 {
+        self.0
+    }
+
+    /// Convert to `f64`, matching the float divide the host decoder performs.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / COORDINATE_SCALE_FACTOR
+    }
+
+    /// Build a `Fixed` from an `f64`, rounding to the nearest representable value.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn from_f64(value: f64) -> Self {
+        Fixed((value * COORDINATE_SCALE_FACTOR).round() as i32)
+    }
+
+    /// Saturating addition in the raw integer domain.
+    pub const fn saturating_add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction in the raw integer domain.
+    pub const fn saturating_sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Saturating fixed-point multiplication.
+    ///
+    /// The product is computed in 64 bits and shifted back down by the
+    /// fractional width before being clamped into `i32`.
+    pub const fn saturating_mul(self, rhs: Fixed) -> Fixed {
+        let product = (self.0 as i64 * rhs.0 as i64) >> Self::FRACTIONAL_BITS;
+        if product > i32::MAX as i64 {
+            Fixed(i32::MAX)
+        } else if product < i32::MIN as i64 {
+            Fixed(i32::MIN)
+        } else {
+            Fixed(product as i32)
+        }
+    }
+}
+
 /// Represents a single point's coordinate data
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CoordinatePoint {
     pub x: Option<f64>,
     pub y: Option<f64>,
@@ -119,6 +355,24 @@ impl CoordinatePoint {
             over_range: None,
         }
     }
+
+    /// X as a Q22.10 [`Fixed`], for exact integer math matching the hardware.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn x_fixed(&self) -> Option<Fixed> {
+        self.x.map(Fixed::from_f64)
+    }
+
+    /// Y as a Q22.10 [`Fixed`].
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn y_fixed(&self) -> Option<Fixed> {
+        self.y.map(Fixed::from_f64)
+    }
+
+    /// Z as a Q22.10 [`Fixed`].
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn z_fixed(&self) -> Option<Fixed> {
+        self.z.map(Fixed::from_f64)
+    }
 }
 
 impl Default for CoordinatePoint {
@@ -127,135 +381,464 @@ impl Default for CoordinatePoint {
     }
 }
 
-/// Collection of coordinate data for multiple points
-#[derive(Debug, Clone)]
-pub struct CoordinateData {
-    pub points: Vec<CoordinatePoint>,
+/// Compact growable bitset, used both for per-column validity masks and for the
+/// bit-packed `gain`/`over_range` value columns.
+///
+/// Packing booleans 64-to-a-word keeps the flag columns eight times smaller than
+/// a `Vec<bool>` and keeps a frame's columns resident in cache during decode.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
 }
 
-impl CoordinateData {
-    /// Create new empty coordinate data
+#[cfg(feature = "alloc")]
+impl BitSet {
+    /// Create an empty bitset.
     pub fn new() -> Self {
+        Self { words: Vec::new(), len: 0 }
+    }
+
+    /// Create an empty bitset sized to hold `capacity` bits without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { words: Vec::with_capacity(capacity.div_ceil(64)), len: 0 }
+    }
+
+    /// Number of bits pushed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no bits have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a bit.
+    pub fn push(&mut self, bit: bool) {
+        let index = self.len;
+        if index / 64 >= self.words.len() {
+            self.words.push(0);
+        }
+        if bit {
+            self.words[index / 64] |= 1u64 << (index % 64);
+        }
+        self.len += 1;
+    }
+
+    /// Read the bit at `index` (`false` when out of range).
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && (self.words[index / 64] >> (index % 64)) & 1 != 0
+    }
+
+    /// Overwrite the bit at `index`.
+    pub fn set(&mut self, index: usize, bit: bool) {
+        if index >= self.len {
+            return;
+        }
+        let mask = 1u64 << (index % 64);
+        if bit {
+            self.words[index / 64] |= mask;
+        } else {
+            self.words[index / 64] &= !mask;
+        }
+    }
+
+    /// Append every bit of `other` onto the end of this bitset.
+    fn extend_from(&mut self, other: &BitSet) {
+        for i in 0..other.len {
+            self.push(other.get(i));
+        }
+    }
+
+    /// Return a new bitset keeping only every `factor`th bit.
+    fn decimated(&self, factor: usize) -> BitSet {
+        let mut out = BitSet::with_capacity(self.len / factor + 1);
+        let mut i = 0;
+        while i < self.len {
+            out.push(self.get(i));
+            i += factor;
+        }
+        out
+    }
+}
+
+/// A single contiguous, nullable column of `T` values.
+///
+/// Values live in one flat [`Vec`], so decoding and downstream passes walk the
+/// buffer linearly; the companion [`BitSet`] records which rows are present,
+/// preserving the per-field `Option` semantics the old array-of-structs
+/// [`CoordinatePoint`] carried.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Column<T> {
+    /// Raw values; slots for absent rows hold `T::default()`.
+    pub values: Vec<T>,
+    /// Validity mask: bit `i` is set when row `i` carries a value.
+    pub valid: BitSet,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy + Default> Column<T> {
+    fn with_capacity(capacity: usize) -> Self {
         Self {
-            points: Vec::new(),
+            values: Vec::with_capacity(capacity),
+            valid: BitSet::with_capacity(capacity),
         }
     }
-    
-    /// Create coordinate data with a specific capacity
-    pub fn with_capacity(capacity: usize) -> Self {
+
+    fn push(&mut self, value: Option<T>) {
+        self.values.push(value.unwrap_or_default());
+        self.valid.push(value.is_some());
+    }
+
+    /// Read row `i` as an `Option`, honouring the validity mask.
+    pub fn get(&self, i: usize) -> Option<T> {
+        if self.valid.get(i) {
+            Some(self.values[i])
+        } else {
+            None
+        }
+    }
+
+    /// Overwrite row `i`, updating the validity mask to match.
+    pub fn set(&mut self, i: usize, value: Option<T>) {
+        if i >= self.values.len() {
+            return;
+        }
+        self.values[i] = value.unwrap_or_default();
+        self.valid.set(i, value.is_some());
+    }
+
+    fn extend_from(&mut self, other: &Column<T>) {
+        self.values.extend_from_slice(&other.values);
+        self.valid.extend_from(&other.valid);
+    }
+
+    fn decimate(&mut self, factor: usize) {
+        self.values = self.values.iter().copied().step_by(factor).collect();
+        self.valid = self.valid.decimated(factor);
+    }
+}
+
+/// A nullable boolean column, bit-packing both the values and the validity mask.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoolColumn {
+    /// Bit-packed values; absent rows read as `false` under the mask.
+    pub values: BitSet,
+    /// Validity mask: bit `i` is set when row `i` carries a value.
+    pub valid: BitSet,
+}
+
+#[cfg(feature = "alloc")]
+impl BoolColumn {
+    fn with_capacity(capacity: usize) -> Self {
         Self {
-            points: Vec::with_capacity(capacity),
+            values: BitSet::with_capacity(capacity),
+            valid: BitSet::with_capacity(capacity),
         }
     }
-    
-    /// Add a point to the coordinate data
-    pub fn add_point(&mut self, point: CoordinatePoint) {
-        self.points.push(point);
+
+    fn push(&mut self, value: Option<bool>) {
+        self.values.push(value.unwrap_or(false));
+        self.valid.push(value.is_some());
     }
-    
-    /// Get the number of points
+
+    /// Read row `i` as an `Option`, honouring the validity mask.
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if self.valid.get(i) {
+            Some(self.values.get(i))
+        } else {
+            None
+        }
+    }
+
+    fn extend_from(&mut self, other: &BoolColumn) {
+        self.values.extend_from(&other.values);
+        self.valid.extend_from(&other.valid);
+    }
+
+    fn decimate(&mut self, factor: usize) {
+        self.values = self.values.decimated(factor);
+        self.valid = self.valid.decimated(factor);
+    }
+}
+
+/// Columnar (structure-of-arrays) coordinate data for a frame's pixels.
+///
+/// Each field is stored as its own contiguous [`Column`]/[`BoolColumn`], and a
+/// column is allocated only when its [`FieldType`] is on the decode whitelist —
+/// so a frame decoded for `x, y, z` carries no intensity/gain buffers at all.
+/// Decoding pushes straight into these columns, Arrow/NumPy export reads each
+/// contiguous buffer in one linear pass, and [`decimate`](Self::decimate) becomes a
+/// strided copy per column. The old per-point view is still available through
+/// [`point`](Self::point) and [`iter`](Self::iter).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoordinateData {
+    len: usize,
+    pub x: Option<Column<f64>>,
+    pub y: Option<Column<f64>>,
+    pub z: Option<Column<f64>>,
+    pub intensity: Option<Column<u16>>,
+    pub gain: Option<BoolColumn>,
+    pub over_range: Option<BoolColumn>,
+}
+
+#[cfg(feature = "alloc")]
+impl CoordinateData {
+    /// Create new empty coordinate data with no columns allocated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate the columns named by `whitelist`, each sized for `capacity` rows.
+    pub fn with_whitelist(whitelist: &FieldWhitelist, capacity: usize) -> Self {
+        Self {
+            len: 0,
+            x: whitelist.includes(&FieldType::X).then(|| Column::with_capacity(capacity)),
+            y: whitelist.includes(&FieldType::Y).then(|| Column::with_capacity(capacity)),
+            z: whitelist.includes(&FieldType::Z).then(|| Column::with_capacity(capacity)),
+            intensity: whitelist
+                .includes(&FieldType::Intensity)
+                .then(|| Column::with_capacity(capacity)),
+            gain: whitelist
+                .includes(&FieldType::Gain)
+                .then(|| BoolColumn::with_capacity(capacity)),
+            over_range: whitelist
+                .includes(&FieldType::OverRange)
+                .then(|| BoolColumn::with_capacity(capacity)),
+        }
+    }
+
+    /// Append one pixel's fields, pushing into each allocated column.
+    ///
+    /// Fields whose column was not allocated (not on the whitelist) are dropped;
+    /// the value passed for them is ignored.
+    pub fn push_row(
+        &mut self,
+        x: Option<f64>,
+        y: Option<f64>,
+        z: Option<f64>,
+        intensity: Option<u16>,
+        gain: Option<bool>,
+        over_range: Option<bool>,
+    ) {
+        if let Some(col) = self.x.as_mut() { col.push(x); }
+        if let Some(col) = self.y.as_mut() { col.push(y); }
+        if let Some(col) = self.z.as_mut() { col.push(z); }
+        if let Some(col) = self.intensity.as_mut() { col.push(intensity); }
+        if let Some(col) = self.gain.as_mut() { col.push(gain); }
+        if let Some(col) = self.over_range.as_mut() { col.push(over_range); }
+        self.len += 1;
+    }
+
+    /// Get the number of points.
     pub fn len(&self) -> usize {
-        self.points.len()
+        self.len
     }
-    
-    /// Check if the coordinate data is empty
+
+    /// Check if the coordinate data is empty.
     pub fn is_empty(&self) -> bool {
-        self.points.is_empty()
+        self.len == 0
     }
-    
-    /// Apply decimation to the coordinate data
+
+    /// Reconstruct the [`CoordinatePoint`] view of row `i`, or `None` if out of
+    /// range. Provided for back-compat with consumers written against the old
+    /// array-of-structs layout.
+    pub fn point(&self, i: usize) -> Option<CoordinatePoint> {
+        if i >= self.len {
+            return None;
+        }
+        Some(CoordinatePoint {
+            x: self.x.as_ref().and_then(|c| c.get(i)),
+            y: self.y.as_ref().and_then(|c| c.get(i)),
+            z: self.z.as_ref().and_then(|c| c.get(i)),
+            intensity: self.intensity.as_ref().and_then(|c| c.get(i)),
+            gain: self.gain.as_ref().and_then(|c| c.get(i)),
+            over_range: self.over_range.as_ref().and_then(|c| c.get(i)),
+        })
+    }
+
+    /// Iterate the points as [`CoordinatePoint`] views (back-compat).
+    pub fn iter(&self) -> impl Iterator<Item = CoordinatePoint> + '_ {
+        (0..self.len).map(move |i| self.point(i).expect("index in range"))
+    }
+
+    /// The `x` column's contiguous values, if the column is allocated.
+    pub fn x_values(&self) -> Option<&[f64]> {
+        self.x.as_ref().map(|c| c.values.as_slice())
+    }
+
+    /// The `y` column's contiguous values, if the column is allocated.
+    pub fn y_values(&self) -> Option<&[f64]> {
+        self.y.as_ref().map(|c| c.values.as_slice())
+    }
+
+    /// The `z` column's contiguous values, if the column is allocated.
+    pub fn z_values(&self) -> Option<&[f64]> {
+        self.z.as_ref().map(|c| c.values.as_slice())
+    }
+
+    /// The `intensity` column's contiguous values, if the column is allocated.
+    pub fn intensity_values(&self) -> Option<&[u16]> {
+        self.intensity.as_ref().map(|c| c.values.as_slice())
+    }
+
+    /// Apply decimation to the coordinate data, keeping every `factor`th row of
+    /// each column.
     pub fn decimate(&mut self, factor: usize) {
         if factor <= 1 {
             return;
         }
-        
-        let decimated_points: Vec<_> = self.points
-            .iter()
-            .step_by(factor)
-            .cloned()
-            .collect();
-        
-        self.points = decimated_points;
+        if let Some(col) = self.x.as_mut() { col.decimate(factor); }
+        if let Some(col) = self.y.as_mut() { col.decimate(factor); }
+        if let Some(col) = self.z.as_mut() { col.decimate(factor); }
+        if let Some(col) = self.intensity.as_mut() { col.decimate(factor); }
+        if let Some(col) = self.gain.as_mut() { col.decimate(factor); }
+        if let Some(col) = self.over_range.as_mut() { col.decimate(factor); }
+        self.len = self.len.div_ceil(factor);
+    }
+
+    /// Append all rows of `other` onto the end of this data, column by column.
+    ///
+    /// Both sides must have the same columns allocated (same whitelist); a
+    /// column present on one side but not the other is left untouched. Used to
+    /// concatenate per-chunk results from parallel extraction back into order.
+    pub fn append(&mut self, other: &CoordinateData) {
+        if let (Some(dst), Some(src)) = (self.x.as_mut(), other.x.as_ref()) { dst.extend_from(src); }
+        if let (Some(dst), Some(src)) = (self.y.as_mut(), other.y.as_ref()) { dst.extend_from(src); }
+        if let (Some(dst), Some(src)) = (self.z.as_mut(), other.z.as_ref()) { dst.extend_from(src); }
+        if let (Some(dst), Some(src)) = (self.intensity.as_mut(), other.intensity.as_ref()) { dst.extend_from(src); }
+        if let (Some(dst), Some(src)) = (self.gain.as_mut(), other.gain.as_ref()) { dst.extend_from(src); }
+        if let (Some(dst), Some(src)) = (self.over_range.as_mut(), other.over_range.as_ref()) { dst.extend_from(src); }
+        self.len += other.len;
     }
 }
 
-impl Default for CoordinateData {
-    fn default() -> Self {
-        Self::new()
+/// A spatial/predicate region of interest evaluated at decode time.
+///
+/// Each axis carries an optional inclusive `(min, max)` bound; a point is kept
+/// only if every bound it names is satisfied. Because the filter is applied
+/// before a point is appended to [`CoordinateData`], rejected points are never
+/// materialised — for a sparse ROI this shrinks both the output arrays and the
+/// peak memory footprint (predicate pushdown).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Roi {
+    pub x: Option<(f64, f64)>,
+    pub y: Option<(f64, f64)>,
+    pub z: Option<(f64, f64)>,
+    pub intensity: Option<(u16, u16)>,
+    /// Keep only points whose over-range flag equals this value, when set.
+    pub over_range: Option<bool>,
+}
+
+#[cfg(feature = "alloc")]
+impl Roi {
+    /// Whether the filter constrains the given axis, so it must be decoded even
+    /// if it is off the field whitelist.
+    fn needs(&self, field: &FieldType) -> bool {
+        match field {
+            FieldType::X => self.x.is_some(),
+            FieldType::Y => self.y.is_some(),
+            FieldType::Z => self.z.is_some(),
+            FieldType::Intensity => self.intensity.is_some(),
+            FieldType::OverRange => self.over_range.is_some(),
+            FieldType::Gain => false,
+        }
+    }
+
+    /// Test a decoded point's values against the configured bounds.
+    fn accepts(
+        &self,
+        x: Option<f64>,
+        y: Option<f64>,
+        z: Option<f64>,
+        intensity: Option<u16>,
+        over_range: Option<bool>,
+    ) -> bool {
+        fn within(value: Option<f64>, bound: Option<(f64, f64)>) -> bool {
+            match bound {
+                None => true,
+                Some((min, max)) => value.map_or(false, |v| v >= min && v <= max),
+            }
+        }
+        within(x, self.x)
+            && within(y, self.y)
+            && within(z, self.z)
+            && match self.intensity {
+                None => true,
+                Some((min, max)) => intensity.map_or(false, |i| i >= min && i <= max),
+            }
+            && match self.over_range {
+                None => true,
+                Some(want) => over_range == Some(want),
+            }
     }
 }
 
-/// Extract coordinate data from a pixel HWORD
-pub fn extract_coordinates_from_hword(hword: &HWord, whitelist: &FieldWhitelist) -> Option<CoordinatePoint> {
+/// Extract one pixel HWORD's whitelisted fields directly into `out`'s columns,
+/// using `layout` to locate each field in the pixel word.
+///
+/// Non-pixel HWORDs are ignored. When `roi` is set the point is tested against
+/// it before anything is appended, and dropped if it falls outside — so only
+/// the axes the ROI constrains are decoded beyond the whitelist. On a kept pixel
+/// this appends exactly one row to every column `out` has allocated, so all
+/// columns stay the same length.
+#[cfg(feature = "alloc")]
+pub fn extract_coordinates_from_hword(
+    hword: &HWord,
+    whitelist: &FieldWhitelist,
+    roi: Option<&Roi>,
+    layout: &FieldLayout,
+    out: &mut CoordinateData,
+) {
     // Only process pixel HWORDs
     if !hword.control_bits.is_pixel() {
-        return None;
+        return;
     }
-    
+
     let data = hword.data_as_u128();
-    let mut point = CoordinatePoint::new();
-    
-    // Extract X coordinate (bits 23:0, 9.10 fixed point, 19 bits, SIGNED)
-    if whitelist.includes(&FieldType::X) {
-        let x_raw = (data & 0x7FFFF) as u32; // 19 bits
-        // Sign extend from 19 bits to 32 bits
-        let x_signed = if x_raw & 0x40000 != 0 {
-            // Negative: set upper bits to 1
-            (x_raw | 0xFFF80000) as i32
-        } else {
-            // Positive: keep as is
-            x_raw as i32
-        };
-        point.x = Some(x_signed as f64 / COORDINATE_SCALE_FACTOR);
-    }
 
-    // Extract Y coordinate (bits 47:24, 9.10 fixed point, 19 bits, SIGNED)
-    if whitelist.includes(&FieldType::Y) {
-        let y_raw = ((data >> 24) & 0x7FFFF) as u32; // 19 bits
-        // Sign extend from 19 bits to 32 bits
-        let y_signed = if y_raw & 0x40000 != 0 {
-            // Negative: set upper bits to 1
-            (y_raw | 0xFFF80000) as i32
-        } else {
-            // Positive: keep as is
-            y_raw as i32
-        };
-        point.y = Some(y_signed as f64 / COORDINATE_SCALE_FACTOR);
-    }
+    // A field is decoded if it is whitelisted or the ROI filters on it.
+    let need = |field: FieldType| {
+        whitelist.includes(&field) || roi.is_some_and(|r| r.needs(&field))
+    };
 
-    // Extract Z coordinate (bits 71:48, 12.10 fixed point, 22 bits, SIGNED)
-    if whitelist.includes(&FieldType::Z) {
-        let z_raw = ((data >> 48) & 0x3FFFFF) as u32; // 22 bits
-        // Sign extend from 22 bits to 32 bits
-        let z_signed = if z_raw & 0x200000 != 0 {
-            // Negative: set upper bits to 1
-            (z_raw | 0xFFC00000) as i32
-        } else {
-            // Positive: keep as is
-            z_raw as i32
-        };
-        point.z = Some(z_signed as f64 / COORDINATE_SCALE_FACTOR);
-    }
-    
-    // Extract Intensity (bits 87:72, 12 bits)
-    if whitelist.includes(&FieldType::Intensity) {
-        let intensity = ((data >> 72) & 0xFFF) as u16; // 12 bits
-        point.intensity = Some(intensity);
-    }
-    
-    // Extract Over-range flag (bit 90)
-    if whitelist.includes(&FieldType::OverRange) {
-        let over_range = ((data >> 90) & 0x1) != 0;
-        point.over_range = Some(over_range);
-    }
-    
-    // Extract HG/LG flag (bit 91) - this is the "gain" field
-    if whitelist.includes(&FieldType::Gain) {
-        let hg_lg = ((data >> 91) & 0x1) != 0;
-        point.gain = Some(hg_lg); // true = LG (Low Gain), false = HG (High Gain)
+    // Coordinates are signed fixed-point; the layout carries the width, sign and
+    // binary point so the bit math is no longer hardcoded per field.
+    let x = need(FieldType::X).then(|| layout.spec(FieldType::X).decode_f64(data));
+    let y = need(FieldType::Y).then(|| layout.spec(FieldType::Y).decode_f64(data));
+    let z = need(FieldType::Z).then(|| layout.spec(FieldType::Z).decode_f64(data));
+
+    let intensity =
+        need(FieldType::Intensity).then(|| layout.spec(FieldType::Intensity).extract_raw(data) as u16);
+
+    // true = LG (Low Gain), false = HG (High Gain)
+    let gain = need(FieldType::Gain).then(|| layout.spec(FieldType::Gain).extract_raw(data) != 0);
+
+    let over_range =
+        need(FieldType::OverRange).then(|| layout.spec(FieldType::OverRange).extract_raw(data) != 0);
+
+    // Predicate pushdown: drop the point before materialising it.
+    if let Some(roi) = roi {
+        if !roi.accepts(x, y, z, intensity, over_range) {
+            return;
+        }
     }
-    
-    Some(point)
+
+    // Append only the whitelisted fields, even if the ROI forced extra decodes.
+    out.push_row(
+        whitelist.includes(&FieldType::X).then(|| x).flatten(),
+        whitelist.includes(&FieldType::Y).then(|| y).flatten(),
+        whitelist.includes(&FieldType::Z).then(|| z).flatten(),
+        whitelist.includes(&FieldType::Intensity).then(|| intensity).flatten(),
+        whitelist.includes(&FieldType::Gain).then(|| gain).flatten(),
+        whitelist.includes(&FieldType::OverRange).then(|| over_range).flatten(),
+    );
 }
 
 #[cfg(test)]
@@ -302,11 +885,134 @@ mod tests {
         hword.remaining_bits = ((test_data >> 88) & 0xF) as u8;
         
         let whitelist = FieldWhitelist::all();
-        let point = extract_coordinates_from_hword(&hword, &whitelist).unwrap();
-        
+        let mut data = CoordinateData::with_whitelist(&whitelist, 1);
+        extract_coordinates_from_hword(&hword, &whitelist, None, &FieldLayout::standard(), &mut data);
+        let point = data.point(0).unwrap();
+
         assert_eq!(point.x, Some(1.0));
         assert_eq!(point.y, Some(2.0));
         assert_eq!(point.z, Some(3.0));
         assert_eq!(point.intensity, Some(100));
     }
+
+    #[test]
+    fn test_roi_pushdown_drops_out_of_bounds() {
+        // X=1.0, Y=2.0, Z=3.0, intensity=100 (see test_coordinate_extraction).
+        let mut hword = HWord {
+            control_bits: ControlBits::FirstPixel,
+            parity: false,
+            data: [0; 11],
+            remaining_bits: 0,
+        };
+        let test_data: u128 = 1024 | (2048 << 24) | (3072 << 48) | (100 << 72);
+        for i in 0..11 {
+            hword.data[i] = ((test_data >> (i * 8)) & 0xFF) as u8;
+        }
+        hword.remaining_bits = ((test_data >> 88) & 0xF) as u8;
+
+        let whitelist = FieldWhitelist::new(&["x", "y"]);
+
+        // A box that contains the point keeps it.
+        let keep = Roi { x: Some((0.0, 2.0)), y: Some((0.0, 3.0)), ..Default::default() };
+        let mut data = CoordinateData::with_whitelist(&whitelist, 1);
+        extract_coordinates_from_hword(&hword, &whitelist, Some(&keep), &FieldLayout::standard(), &mut data);
+        assert_eq!(data.len(), 1);
+
+        // A box that excludes it on z decodes z (not whitelisted here) and drops.
+        let drop = Roi { z: Some((10.0, 20.0)), ..Default::default() };
+        let mut data = CoordinateData::with_whitelist(&whitelist, 1);
+        extract_coordinates_from_hword(&hword, &whitelist, Some(&drop), &FieldLayout::standard(), &mut data);
+        assert_eq!(data.len(), 0);
+    }
+
+    #[test]
+    fn test_columns_allocated_per_whitelist() {
+        // Only whitelisted columns are allocated; the rest stay `None`.
+        let whitelist = FieldWhitelist::new(&["x", "y"]);
+        let data = CoordinateData::with_whitelist(&whitelist, 4);
+        assert!(data.x.is_some());
+        assert!(data.y.is_some());
+        assert!(data.z.is_none());
+        assert!(data.intensity.is_none());
+    }
+
+    #[test]
+    fn test_columnar_decimate_strides_each_column() {
+        let whitelist = FieldWhitelist::new(&["x", "intensity"]);
+        let mut data = CoordinateData::with_whitelist(&whitelist, 6);
+        for i in 0..6 {
+            data.push_row(Some(i as f64), None, None, Some(i as u16), None, None);
+        }
+        data.decimate(2);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.x_values(), Some(&[0.0, 2.0, 4.0][..]));
+        assert_eq!(data.intensity_values(), Some(&[0u16, 2, 4][..]));
+    }
+
+    #[test]
+    fn test_field_layout_rejects_bad_specs() {
+        // Width outside 1..=32.
+        let mut specs = FieldLayout::standard().specs;
+        specs[FieldType::X.index()].width = 40;
+        assert!(matches!(
+            FieldLayout::new(specs),
+            Err(FieldLayoutError::BadWidth { .. })
+        ));
+
+        // Field running past the 128-bit word.
+        let mut specs = FieldLayout::standard().specs;
+        specs[FieldType::Z.index()] = FieldSpec { offset: 120, width: 16, signed: true, fractional_bits: 10 };
+        assert!(matches!(
+            FieldLayout::new(specs),
+            Err(FieldLayoutError::OutOfRange { .. })
+        ));
+
+        // Two fields claiming the same bits.
+        let mut specs = FieldLayout::standard().specs;
+        specs[FieldType::Y.index()] = specs[FieldType::X.index()];
+        assert!(matches!(
+            FieldLayout::new(specs),
+            Err(FieldLayoutError::Overlap { .. })
+        ));
+    }
+
+    #[test]
+    fn test_field_layout_standard_decodes_reference_pixel() {
+        // Same known pixel as test_coordinate_extraction, decoded field-by-field.
+        let data: u128 = 1024 | (2048 << 24) | (3072 << 48) | (100 << 72);
+        let layout = FieldLayout::standard();
+        assert_eq!(layout.spec(FieldType::X).decode_f64(data), 1.0);
+        assert_eq!(layout.spec(FieldType::Y).decode_f64(data), 2.0);
+        assert_eq!(layout.spec(FieldType::Z).decode_f64(data), 3.0);
+        assert_eq!(layout.spec(FieldType::Intensity).extract_raw(data), 100);
+    }
+
+    #[test]
+    fn test_fixed_raw_roundtrip() {
+        for raw in [0, 1, -1, 1024, -2048, 262143, -262144, i32::MAX, i32::MIN] {
+            assert_eq!(Fixed::from_raw(raw).to_raw(), raw);
+        }
+    }
+
+    #[test]
+    fn test_fixed_matches_float_math() {
+        // Fixed::to_f64 must agree bit-exactly with the `raw / SCALE` divide the
+        // f64 extraction path performs.
+        for raw in [0, 1024, 2048, 3072, -1024, 262143, -262144] {
+            let expected = raw as f64 / COORDINATE_SCALE_FACTOR;
+            assert_eq!(Fixed::from_raw(raw).to_f64(), expected);
+        }
+    }
+
+    #[test]
+    fn test_fixed_saturating_ops() {
+        let one = Fixed::from_raw(Fixed::SCALE);
+        let two = Fixed::from_raw(2 * Fixed::SCALE);
+        assert_eq!(one.saturating_add(one), two);
+        assert_eq!(two.saturating_sub(one), one);
+        assert_eq!(two.saturating_mul(two), Fixed::from_raw(4 * Fixed::SCALE));
+        // Multiplication that overflows i32 clamps instead of wrapping.
+        let big = Fixed::from_raw(i32::MAX);
+        assert_eq!(big.saturating_mul(big), Fixed::from_raw(i32::MAX));
+    }
 }
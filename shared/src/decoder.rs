@@ -0,0 +1,241 @@
+/*!
+Streaming frame decoder over an arbitrary byte source.
+
+Whole-frame parsing ([`Frame::from_bytes`](crate::frame::Frame::from_bytes))
+needs the complete capture in memory first. [`FrameDecoderReader`] instead wraps
+any [`Read`] — a socket, file, or pipe — and pulls one 12-byte HWORD at a time,
+validating control bits and parity as it goes and surfacing decoded points the
+moment a full pixel word lands. A partial word is buffered across calls, so the
+adapter works on unbounded live streams with bounded memory.
+
+The type mirrors the `DecoderReader` adapter pattern: it is itself a [`Read`]
+whose output is the decoded point payload stream (each pixel's 92-bit data field
+as 12 big-endian bytes, matching the integrity-trailer layout), so it slots into
+[`std::io::copy`]/[`std::io::BufReader`] machinery. For structured consumption,
+iterate [`FrameDecoderReader::points`] or call
+[`next_point`](FrameDecoderReader::next_point) directly.
+*/
+
+use std::io::{self, Read};
+
+use crate::coordinates::{extract_coordinates_from_hword, CoordinateData, CoordinatePoint, FieldLayout, FieldWhitelist};
+use crate::hword::{Endianness, HWord};
+use crate::protocol::HWORD_SIZE_BYTES;
+
+/// Incremental decoder that pulls HWORDs out of an inner reader.
+pub struct FrameDecoderReader<R: Read> {
+    inner: R,
+    endianness: Endianness,
+    whitelist: FieldWhitelist,
+    /// Bytes of the in-progress word accumulated across short reads.
+    word: [u8; HWORD_SIZE_BYTES],
+    filled: usize,
+    /// Decoded-payload bytes awaiting drain by the [`Read`] impl.
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl<R: Read> FrameDecoderReader<R> {
+    /// Wrap `inner`, decoding big-endian words and extracting every field.
+    pub fn new(inner: R) -> Self {
+        Self::with_options(inner, Endianness::Big, FieldWhitelist::all())
+    }
+
+    /// Wrap `inner` with an explicit byte order and field whitelist.
+    pub fn with_options(inner: R, endianness: Endianness, whitelist: FieldWhitelist) -> Self {
+        Self {
+            inner,
+            endianness,
+            whitelist,
+            word: [0u8; HWORD_SIZE_BYTES],
+            filled: 0,
+            pending: Vec::new(),
+            pending_pos: 0,
+        }
+    }
+
+    /// Pull and validate the next HWORD, header or pixel.
+    ///
+    /// Returns `Ok(None)` on a clean end of stream at a word boundary. A stream
+    /// that ends mid-word, or a word with bad control bits or parity, is an
+    /// [`io::ErrorKind::InvalidData`]/[`io::ErrorKind::UnexpectedEof`] error.
+    fn next_hword(&mut self) -> io::Result<Option<HWord>> {
+        while self.filled < HWORD_SIZE_BYTES {
+            let n = self.inner.read(&mut self.word[self.filled..])?;
+            if n == 0 {
+                if self.filled == 0 {
+                    return Ok(None);
+                }
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream ended in the middle of a 12-byte HWORD",
+                ));
+            }
+            self.filled += n;
+        }
+        self.filled = 0;
+
+        let hword = HWord::from_bytes_with_order(&self.word, self.endianness)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        if !hword.verify_parity() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "HWORD failed odd-parity check",
+            ));
+        }
+        Ok(Some(hword))
+    }
+
+    /// Decode the next pixel point, skipping header words.
+    ///
+    /// Returns `Ok(None)` once the underlying stream is exhausted.
+    pub fn next_point(&mut self) -> io::Result<Option<CoordinatePoint>> {
+        loop {
+            match self.next_hword()? {
+                None => return Ok(None),
+                Some(hword) => {
+                    let mut scratch = CoordinateData::with_whitelist(&self.whitelist, 1);
+                    extract_coordinates_from_hword(
+                        &hword,
+                        &self.whitelist,
+                        None,
+                        &FieldLayout::standard(),
+                        &mut scratch,
+                    );
+                    if let Some(point) = scratch.point(0) {
+                        return Ok(Some(point));
+                    }
+                    // Header, idle or reserved word (no pixel data): keep pulling
+                    // until the next point materializes.
+                }
+            }
+        }
+    }
+
+    /// Borrowing iterator over the decoded points.
+    pub fn points(&mut self) -> Points<'_, R> {
+        Points { decoder: self }
+    }
+}
+
+/// Iterator returned by [`FrameDecoderReader::points`].
+pub struct Points<'a, R: Read> {
+    decoder: &'a mut FrameDecoderReader<R>,
+}
+
+impl<R: Read> Iterator for Points<'_, R> {
+    type Item = io::Result<CoordinatePoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next_point().transpose()
+    }
+}
+
+impl<R: Read> Read for FrameDecoderReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // Refill the pending payload buffer from the next pixel word when drained.
+        if self.pending_pos >= self.pending.len() {
+            loop {
+                match self.next_hword()? {
+                    None => return Ok(0),
+                    Some(hword) => {
+                        if hword.control_bits.is_pixel() {
+                            let data = hword.data_as_u128() & ((1u128 << 92) - 1);
+                            self.pending.clear();
+                            self.pending.extend_from_slice(&data.to_be_bytes()[4..16]);
+                            self.pending_pos = 0;
+                            break;
+                        }
+                        // Skip header words; they carry no payload.
+                    }
+                }
+            }
+        }
+        let remaining = &self.pending[self.pending_pos..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pending_pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hword::ControlBits;
+
+    /// Build a pixel HWORD carrying `data` in its low bits, with correct parity.
+    fn pixel_word(data: u128) -> [u8; 12] {
+        let mut hword = HWord {
+            control_bits: ControlBits::FirstPixel,
+            parity: false,
+            data: [0u8; 11],
+            remaining_bits: 0,
+        };
+        let masked = data & ((1u128 << 92) - 1);
+        for i in 0..11 {
+            hword.data[i] = ((masked >> (i * 8)) & 0xFF) as u8;
+        }
+        hword.remaining_bits = ((masked >> 88) & 0xF) as u8;
+        // Set odd parity over the reconstructed word.
+        let mut bytes = hword.to_bytes();
+        let ones: u32 = bytes.iter().map(|b| b.count_ones()).sum();
+        if ones % 2 == 0 {
+            hword.parity = true;
+            bytes = hword.to_bytes();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_streams_points_across_short_reads() {
+        // Two pixel words fed through a reader that hands out one byte per call.
+        struct OneByteReader(std::vec::IntoIter<u8>);
+        impl Read for OneByteReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.next() {
+                    Some(b) => {
+                        buf[0] = b;
+                        Ok(1)
+                    }
+                    None => Ok(0),
+                }
+            }
+        }
+
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&pixel_word(0x1234));
+        raw.extend_from_slice(&pixel_word(0x5678));
+
+        let mut decoder = FrameDecoderReader::new(OneByteReader(raw.into_iter()));
+        let mut count = 0;
+        while let Some(point) = decoder.next_point().unwrap() {
+            assert!(point.x.is_some());
+            count += 1;
+        }
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_midword_truncation_is_error() {
+        let mut raw = pixel_word(0x42).to_vec();
+        raw.truncate(7); // half an HWORD
+        let mut decoder = FrameDecoderReader::new(raw.as_slice());
+        assert!(decoder.next_point().is_err());
+    }
+
+    #[test]
+    fn test_read_impl_yields_payload_bytes() {
+        let raw = pixel_word(0xABCD).to_vec();
+        let mut decoder = FrameDecoderReader::new(raw.as_slice());
+        let mut out = Vec::new();
+        io::copy(&mut decoder, &mut out).unwrap();
+        // One pixel -> 12 payload bytes, big-endian low-92-bit field.
+        assert_eq!(out.len(), 12);
+        let value = out.iter().fold(0u128, |acc, &b| (acc << 8) | b as u128);
+        assert_eq!(value, 0xABCD);
+    }
+}
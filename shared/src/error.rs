@@ -4,8 +4,11 @@ Common error types for the Universal Instrument Control Rust components.
 
 use thiserror::Error;
 
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
 /// Common result type used throughout the shared library
-pub type Result<T> = std::result::Result<T, SharedError>;
+pub type Result<T> = core::result::Result<T, SharedError>;
 
 /// Comprehensive error type for all shared operations
 #[derive(Error, Debug)]
@@ -13,36 +16,53 @@ pub enum SharedError {
     /// HWORD parsing errors
     #[error("HWORD error: {0}")]
     HWord(#[from] crate::hword::HWordError),
-    
+
     /// I/O errors (file operations, etc.)
+    #[cfg(feature = "std")]
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
-    
+
     /// Serialization/deserialization errors
+    #[cfg(feature = "std")]
     #[error("Serialization error: {0}")]
     Serde(#[from] serde_json::Error),
-    
+
     /// Invalid frame data
+    #[cfg(feature = "alloc")]
     #[error("Invalid frame data: {0}")]
     InvalidFrame(String),
-    
+
     /// Invalid coordinate data
+    #[cfg(feature = "alloc")]
     #[error("Invalid coordinate data: {0}")]
     InvalidCoordinates(String),
-    
+
     /// File format errors
+    #[cfg(feature = "alloc")]
     #[error("Invalid file format: {0}")]
     InvalidFileFormat(String),
-    
+
     /// Configuration errors
+    #[cfg(feature = "alloc")]
     #[error("Configuration error: {0}")]
     Config(String),
-    
+
+    /// A frame declares a format version this build cannot decode
+    #[error("Unsupported frame format version: {0}")]
+    UnsupportedVersion(u16),
+
+    /// The SHA-512 integrity trailer did not match the frame payload
+    #[cfg(feature = "integrity")]
+    #[error("Frame integrity digest mismatch")]
+    DigestMismatch,
+
     /// Generic errors with context
+    #[cfg(feature = "alloc")]
     #[error("Error: {0}")]
     Generic(String),
 }
 
+#[cfg(feature = "alloc")]
 impl SharedError {
     /// Create a new generic error with a message
     pub fn new(msg: impl Into<String>) -> Self {
@@ -6,16 +6,72 @@ the system for representing complete frames with headers and pixel data.
 */
 
 use crate::hword::{HWord, ControlBits};
-use crate::coordinates::{CoordinateData, CoordinatePoint, FieldWhitelist, extract_coordinates_from_hword};
+use crate::coordinates::{CoordinateData, FieldLayout, FieldWhitelist, Roi, extract_coordinates_from_hword};
 use crate::error::{SharedError, Result};
+use crate::clock::{Clocks, Timestamp};
+use alloc::sync::Arc;
 use serde::{Deserialize, Serialize};
+
+use alloc::{collections::VecDeque, format, string::{String, ToString}, vec::Vec};
+
+#[cfg(feature = "std")]
 use std::path::Path;
 
+/// How a frame's pixel payloads are encoded on the wire.
+///
+/// Stored as the first byte of a serialized frame so the parser can tell a
+/// fixed 96-bit-word stream from a [`Leb128`](PayloadEncoding::Leb128)-compressed
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PayloadEncoding {
+    /// Classic fixed 96-bit HWORDs.
+    Fixed96 = 0,
+    /// Variable-length LEB128 pixel payloads.
+    Leb128 = 1,
+}
+
+/// Register-map describing where named header fields live in the flat
+/// 5-registers-per-HWORD stream produced by [`FrameHeader::extract_registers`].
+///
+/// Different instrument firmware revisions lay their header registers out
+/// differently, so the offsets are data rather than constants: load a
+/// [`HeaderLayout`] from config to support a new revision without recompiling.
+/// Each field names the index of its first register; [`expected_pixel_count`]
+/// spans two registers (low word first) so counts above `u16::MAX` fit.
+///
+/// [`expected_pixel_count`]: HeaderLayout::expected_pixel_count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderLayout {
+    /// Index of the low register of the 32-bit expected pixel count.
+    pub expected_pixel_count: usize,
+    /// Index of the frame width register.
+    pub frame_width: usize,
+    /// Index of the frame height register.
+    pub frame_height: usize,
+    /// Index of the scan-mode register.
+    pub scan_mode: usize,
+}
+
+impl Default for HeaderLayout {
+    /// The layout of the current reference firmware revision.
+    fn default() -> Self {
+        Self {
+            expected_pixel_count: 0,
+            frame_width: 2,
+            frame_height: 3,
+            scan_mode: 4,
+        }
+    }
+}
+
 /// Frame header containing register data and metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameHeader {
     pub hwords: Vec<HWord>,
     pub registers: Vec<u16>, // Extracted register values
+    /// Layout version this frame was decoded as (see [`crate::protocol::FORMAT_VERSION`]).
+    pub version: u16,
 }
 
 impl FrameHeader {
@@ -24,6 +80,7 @@ impl FrameHeader {
         Self {
             hwords: Vec::new(),
             registers: Vec::new(),
+            version: crate::protocol::FORMAT_VERSION,
         }
     }
     
@@ -59,6 +116,34 @@ impl FrameHeader {
         
         Ok(())
     }
+
+    /// Read a single 16-bit register by index, if present.
+    pub fn register(&self, index: usize) -> Option<u16> {
+        self.registers.get(index).copied()
+    }
+
+    /// Header-declared expected pixel count, read as a 32-bit value across the
+    /// two registers named by `layout` (low word first).
+    pub fn expected_pixel_count(&self, layout: &HeaderLayout) -> Option<u32> {
+        let lo = self.register(layout.expected_pixel_count)? as u32;
+        let hi = self.register(layout.expected_pixel_count + 1)? as u32;
+        Some(lo | (hi << 16))
+    }
+
+    /// Header-declared frame width in pixels.
+    pub fn frame_width(&self, layout: &HeaderLayout) -> Option<u16> {
+        self.register(layout.frame_width)
+    }
+
+    /// Header-declared frame height in pixels.
+    pub fn frame_height(&self, layout: &HeaderLayout) -> Option<u16> {
+        self.register(layout.frame_height)
+    }
+
+    /// Header-declared scan mode.
+    pub fn scan_mode(&self, layout: &HeaderLayout) -> Option<u16> {
+        self.register(layout.scan_mode)
+    }
 }
 
 impl Default for FrameHeader {
@@ -68,7 +153,7 @@ impl Default for FrameHeader {
 }
 
 /// Pixel data containing coordinate information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PixelData {
     pub hwords: Vec<HWord>,
 }
@@ -102,20 +187,92 @@ impl PixelData {
     }
     
     /// Extract coordinate data from pixel HWORDs
-    pub fn extract_coordinates(&self, whitelist: &FieldWhitelist, decimation: usize) -> CoordinateData {
-        let mut coordinates = CoordinateData::with_capacity(self.hwords.len() / decimation.max(1));
-        
+    pub fn extract_coordinates(
+        &self,
+        whitelist: &FieldWhitelist,
+        decimation: usize,
+        roi: Option<&Roi>,
+    ) -> CoordinateData {
+        let layout = FieldLayout::standard();
+        let mut coordinates =
+            CoordinateData::with_whitelist(whitelist, self.hwords.len() / decimation.max(1));
+
         for (i, hword) in self.hwords.iter().enumerate() {
             // Apply decimation
             if decimation > 1 && i % decimation != 0 {
                 continue;
             }
-            
-            if let Some(point) = extract_coordinates_from_hword(hword, whitelist) {
-                coordinates.add_point(point);
-            }
+
+            extract_coordinates_from_hword(hword, whitelist, roi, &layout, &mut coordinates);
+        }
+
+        coordinates
+    }
+
+    /// Extract coordinate data across a rayon thread pool.
+    ///
+    /// The decimated pixel HWORDs are split into `threads` contiguous chunks,
+    /// each decoded into its own per-chunk [`CoordinateData`] in parallel, then
+    /// concatenated in order. Each HWORD decode is independent, so wall-clock
+    /// time for the extraction phase falls roughly linearly with core count.
+    /// `threads == 0` uses rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn extract_coordinates_parallel(
+        &self,
+        whitelist: &FieldWhitelist,
+        decimation: usize,
+        roi: Option<&Roi>,
+        threads: usize,
+    ) -> CoordinateData {
+        use rayon::prelude::*;
+
+        let layout = FieldLayout::standard();
+        let decimation = decimation.max(1);
+        let selected: Vec<&HWord> = self
+            .hwords
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| decimation <= 1 || i % decimation == 0)
+            .map(|(_, hword)| hword)
+            .collect();
+
+        let worker_count = if threads == 0 {
+            rayon::current_num_threads()
+        } else {
+            threads
+        }
+        .max(1);
+        let chunk_size = selected.len().div_ceil(worker_count).max(1);
+
+        let extract_chunks = || {
+            selected
+                .par_chunks(chunk_size)
+                .map(|chunk| {
+                    let mut data = CoordinateData::with_whitelist(whitelist, chunk.len());
+                    for hword in chunk {
+                        extract_coordinates_from_hword(hword, whitelist, roi, &layout, &mut data);
+                    }
+                    data
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // A non-zero `threads` pins the work to a local pool of that size;
+        // otherwise the caller's ambient (global) pool is used.
+        let parts = if threads == 0 {
+            extract_chunks()
+        } else {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map(|pool| pool.install(extract_chunks))
+                .unwrap_or_else(|_| extract_chunks())
+        };
+
+        let mut coordinates = CoordinateData::with_whitelist(whitelist, selected.len());
+        for part in &parts {
+            coordinates.append(part);
         }
-        
         coordinates
     }
 }
@@ -127,12 +284,14 @@ impl Default for PixelData {
 }
 
 /// Complete frame with header and pixel data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Frame {
     pub frame_id: u32,
     pub header: FrameHeader,
     pub pixels: PixelData,
     pub frame_type: String,
+    /// Clock readings taken when this frame completed, if a clock was supplied.
+    pub timestamp: Option<Timestamp>,
 }
 
 impl Frame {
@@ -143,10 +302,12 @@ impl Frame {
             header: FrameHeader::new(),
             pixels: PixelData::new(),
             frame_type: "point_cloud".to_string(),
+            timestamp: None,
         }
     }
     
     /// Load a frame from a .dsql file
+    #[cfg(feature = "std")]
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         
@@ -159,24 +320,139 @@ impl Frame {
         // Parse HWORDs from the raw data
         Self::from_bytes(frame_id, &data)
     }
-    
-    /// Create a frame from raw bytes
+
+    /// Freeze this frame as a checked-in golden test vector.
+    ///
+    /// Writes a deterministic JSON document holding the frame id, the extracted
+    /// header registers, every HWORD as a 24-character big-endian hex string, and
+    /// the decoded [`CoordinateData`]. A captured `.dsql` can be frozen once and
+    /// diffed against on every run to catch parsing or decoding regressions
+    /// without keeping the original binary around.
+    #[cfg(feature = "std")]
+    pub fn to_vector_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let vector = FrameVector::from_frame(self);
+        let document = serde_json::to_string_pretty(&vector)?;
+        std::fs::write(path, document)?;
+        Ok(())
+    }
+
+    /// Load a frame from a golden test vector written by [`to_vector_file`](Self::to_vector_file).
+    ///
+    /// The HWORDs are replayed through [`from_bytes`](Self::from_bytes) to rebuild
+    /// an identical frame, then the freshly extracted registers and decoded
+    /// coordinates are checked against the values frozen in the document. A
+    /// mismatch means the parser or decoder has drifted and is reported as an
+    /// [`SharedError::InvalidFrame`].
+    #[cfg(feature = "std")]
+    pub fn from_vector_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let document = std::fs::read_to_string(path)?;
+        let vector: FrameVector = serde_json::from_str(&document)?;
+
+        let mut bytes = Vec::with_capacity(vector.hwords.len() * crate::protocol::HWORD_SIZE_BYTES);
+        for hex in &vector.hwords {
+            bytes.extend_from_slice(&decode_hword_hex(hex)?);
+        }
+
+        let frame = Self::from_bytes(vector.frame_id, &bytes)?;
+
+        if frame.header.registers != vector.registers {
+            return Err(SharedError::invalid_frame(
+                "test vector register mismatch: decoder output differs from frozen vector",
+            ));
+        }
+        let coordinates = frame.pixels.extract_coordinates(&FieldWhitelist::all(), 1, None);
+        if coordinates != vector.coordinates {
+            return Err(SharedError::invalid_frame(
+                "test vector coordinate mismatch: decoder output differs from frozen vector",
+            ));
+        }
+        Ok(frame)
+    }
+
+    /// Serialize the 92-bit data payloads of all pixel points, in order, each as
+    /// 12 big-endian bytes (the top 4 bits are always zero). This byte stream is
+    /// what the SHA-512 integrity trailer is computed over; the generator and the
+    /// parser must agree on it exactly.
+    #[cfg(feature = "integrity")]
+    fn integrity_payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(self.pixels.hwords.len() * 12);
+        for hword in &self.pixels.hwords {
+            let data = hword.data_as_u128() & ((1u128 << 92) - 1);
+            // The low 92 bits fit in 12 bytes; `to_be_bytes()` is 16 wide, so the
+            // leading 4 bytes are zero and dropped.
+            payload.extend_from_slice(&data.to_be_bytes()[4..16]);
+        }
+        payload
+    }
+
+    /// Compute the SHA-512 integrity digest over this frame's pixel payloads.
+    #[cfg(feature = "integrity")]
+    pub fn integrity_digest(&self) -> [u8; 64] {
+        crate::sha512::sha512(&self.integrity_payload())
+    }
+
+    /// Parse a frame whose trailing 64 bytes are a SHA-512 integrity trailer,
+    /// verifying the digest against the decoded pixel payloads.
+    ///
+    /// Returns [`SharedError::DigestMismatch`] if the recomputed digest does not
+    /// match the trailer.
+    #[cfg(feature = "integrity")]
+    pub fn from_bytes_with_integrity(
+        frame_id: u32,
+        data: &[u8],
+        endianness: crate::hword::Endianness,
+    ) -> Result<Self> {
+        if data.len() < 64 {
+            return Err(SharedError::invalid_file_format(
+                "Frame too short to contain a 64-byte integrity trailer",
+            ));
+        }
+        let (body, trailer) = data.split_at(data.len() - 64);
+        let frame = Self::from_bytes_with_order(frame_id, body, endianness)?;
+        if frame.integrity_digest().as_slice() != trailer {
+            return Err(SharedError::DigestMismatch);
+        }
+        Ok(frame)
+    }
+
+    /// Create a frame from raw big-endian bytes.
     pub fn from_bytes(frame_id: u32, data: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_order(frame_id, data, crate::hword::Endianness::Big)
+    }
+
+    /// Create a frame from raw big-endian bytes, stamping it with `clock` as it
+    /// completes so callers get a reproducible acquisition [`Timestamp`].
+    pub fn from_bytes_with_clock(
+        frame_id: u32,
+        data: &[u8],
+        clock: &dyn Clocks,
+    ) -> Result<Self> {
+        let mut frame = Self::from_bytes(frame_id, data)?;
+        frame.timestamp = Some(clock.now());
+        Ok(frame)
+    }
+
+    /// Create a frame from raw bytes, interpreting each HWORD in `endianness`.
+    pub fn from_bytes_with_order(
+        frame_id: u32,
+        data: &[u8],
+        endianness: crate::hword::Endianness,
+    ) -> Result<Self> {
         if data.len() % crate::protocol::HWORD_SIZE_BYTES != 0 {
             return Err(SharedError::invalid_file_format(
                 format!("File size {} is not a multiple of HWORD size (12 bytes)", data.len())
             ));
         }
-        
+
         let mut frame = Frame::new(frame_id);
         let mut in_header = false;
-        
+
         // Process each 12-byte HWORD
         for chunk in data.chunks_exact(crate::protocol::HWORD_SIZE_BYTES) {
             let hword_bytes: [u8; 12] = chunk.try_into()
                 .map_err(|_| SharedError::invalid_file_format("Invalid HWORD chunk size"))?;
-            
-            let hword = HWord::from_bytes(&hword_bytes)?;
+
+            let hword = HWord::from_bytes_with_order(&hword_bytes, endianness)?;
             
             match hword.control_bits {
                 ControlBits::FirstHeader => {
@@ -216,6 +492,85 @@ impl Frame {
         Ok(frame)
     }
     
+    /// Serialize this frame with LEB128-compressed pixel payloads.
+    ///
+    /// Layout: a one-byte [`PayloadEncoding`] flag, the header HWORDs verbatim
+    /// (they are few and fixed-width), then the pixel count and, per pixel, a
+    /// control/parity byte followed by the LEB128-encoded 92-bit data value.
+    /// Frames dominated by small readings shrink substantially versus the fixed
+    /// 96-bit-per-point form.
+    pub fn to_leb128_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.push(PayloadEncoding::Leb128 as u8);
+
+        // Header HWORDs, fixed 12 bytes each, preceded by their count.
+        crate::leb128::encode_unsigned(self.header.hwords.len() as u128, &mut out);
+        for hword in &self.header.hwords {
+            out.extend_from_slice(&hword.to_bytes());
+        }
+
+        // Pixel payloads as LEB128.
+        crate::leb128::encode_unsigned(self.pixels.hwords.len() as u128, &mut out);
+        for hword in &self.pixels.hwords {
+            // Low 3 bits = control bits, bit 3 = parity, so a pixel roundtrips
+            // exactly without recomputing parity.
+            let control_byte = (hword.control_bits as u8) | ((hword.parity as u8) << 3);
+            out.push(control_byte);
+            crate::leb128::encode_unsigned(hword.data_as_u128(), &mut out);
+        }
+        out
+    }
+
+    /// Decode a frame written by [`to_leb128_bytes`](Self::to_leb128_bytes).
+    ///
+    /// Rejects a stream whose flag byte is not [`PayloadEncoding::Leb128`], and
+    /// propagates the LEB128 guard errors for truncated or runaway values.
+    pub fn from_leb128_bytes(frame_id: u32, data: &[u8]) -> Result<Self> {
+        let mut pos = 0;
+        let flag = *data.first().ok_or_else(|| {
+            SharedError::invalid_file_format("Empty compressed frame")
+        })?;
+        pos += 1;
+        if flag != PayloadEncoding::Leb128 as u8 {
+            return Err(SharedError::invalid_file_format(
+                "Frame is not LEB128-encoded",
+            ));
+        }
+
+        let mut frame = Frame::new(frame_id);
+
+        let (header_count, used) = crate::leb128::decode_unsigned(&data[pos..])?;
+        pos += used;
+        for _ in 0..header_count {
+            let end = pos + crate::protocol::HWORD_SIZE_BYTES;
+            let chunk: [u8; 12] = data.get(pos..end)
+                .and_then(|s| s.try_into().ok())
+                .ok_or_else(|| SharedError::invalid_file_format("Truncated header HWORD"))?;
+            frame.header.add_hword(HWord::from_bytes(&chunk)?)?;
+            pos = end;
+        }
+
+        let (pixel_count, used) = crate::leb128::decode_unsigned(&data[pos..])?;
+        pos += used;
+        for _ in 0..pixel_count {
+            let control_byte = *data.get(pos).ok_or_else(|| {
+                SharedError::invalid_file_format("Truncated pixel control byte")
+            })?;
+            pos += 1;
+            let (value, used) = crate::leb128::decode_unsigned(&data[pos..])?;
+            pos += used;
+
+            let control_bits = (control_byte & 0x7) as u128;
+            let parity_bit = ((control_byte >> 3) & 0x1) as u128;
+            let word = (control_bits << 93) | (parity_bit << 92) | (value & ((1u128 << 92) - 1));
+            let bytes = crate::hword::Endianness::Big.write_word96(word);
+            frame.pixels.add_hword(HWord::from_bytes(&bytes)?)?;
+        }
+
+        frame.header.extract_registers()?;
+        Ok(frame)
+    }
+
     /// Get the frame number
     pub fn number(&self) -> u32 {
         self.frame_id
@@ -226,28 +581,146 @@ impl Frame {
         &self.frame_type
     }
     
-    /// Get the expected number of pixels (from header data if available)
+    /// Get the expected number of pixels, as declared by the header registers.
+    ///
+    /// Uses the reference [`HeaderLayout`]; falls back to the actual pixel count
+    /// when the header carries no declared count. Comparing this against
+    /// [`pixels.len()`](PixelData::len) is how callers spot truncated or
+    /// dropped-pixel frames.
     pub fn num_pixels(&self) -> usize {
-        // For now, return the actual number of pixel HWORDs
-        // In the future, this could be extracted from header registers
-        self.pixels.len()
+        self.num_pixels_with_layout(&HeaderLayout::default())
+    }
+
+    /// As [`num_pixels`](Self::num_pixels), but with an explicit register map for
+    /// firmware revisions whose header layout differs from the reference.
+    pub fn num_pixels_with_layout(&self, layout: &HeaderLayout) -> usize {
+        self.header
+            .expected_pixel_count(layout)
+            .map(|count| count as usize)
+            .unwrap_or_else(|| self.pixels.len())
     }
     
-    /// Extract coordinate data with optional decimation and field filtering
-    pub fn data(&self, decimation: Option<usize>, field_whitelist: Option<&[&str]>) -> CoordinateData {
+    /// Extract coordinate data with optional decimation, field filtering, and a
+    /// region-of-interest predicate applied at decode time.
+    pub fn data(
+        &self,
+        decimation: Option<usize>,
+        field_whitelist: Option<&[&str]>,
+        roi: Option<&Roi>,
+    ) -> CoordinateData {
         let decimation = decimation.unwrap_or(1);
-        
+
         let whitelist = if let Some(fields) = field_whitelist {
             FieldWhitelist::new(fields)
         } else {
             FieldWhitelist::all()
         };
-        
-        self.pixels.extract_coordinates(&whitelist, decimation)
+
+        self.pixels.extract_coordinates(&whitelist, decimation, roi)
+    }
+
+    /// As [`data`](Self::data), but decodes the pixel HWORDs in parallel across a
+    /// rayon thread pool. `threads == 0` uses rayon's global pool; any other
+    /// value pins the work to a local pool of that size.
+    #[cfg(feature = "parallel")]
+    pub fn data_parallel(
+        &self,
+        decimation: Option<usize>,
+        field_whitelist: Option<&[&str]>,
+        roi: Option<&Roi>,
+        threads: usize,
+    ) -> CoordinateData {
+        let decimation = decimation.unwrap_or(1);
+
+        let whitelist = if let Some(fields) = field_whitelist {
+            FieldWhitelist::new(fields)
+        } else {
+            FieldWhitelist::all()
+        };
+
+        self.pixels
+            .extract_coordinates_parallel(&whitelist, decimation, roi, threads)
+    }
+
+    /// Serialize this frame's decoded pixels to `w` in the given [`Format`],
+    /// including only the whitelisted fields.
+    ///
+    /// [`Format`]: crate::format::Format
+    #[cfg(feature = "std")]
+    pub fn write_as(
+        &self,
+        format: crate::format::Format,
+        field_whitelist: Option<&[&str]>,
+        w: &mut impl std::io::Write,
+    ) -> std::io::Result<()> {
+        let whitelist = match field_whitelist {
+            Some(fields) => FieldWhitelist::new(fields),
+            None => FieldWhitelist::all(),
+        };
+        crate::format::encoder_for(format).encode(self, &whitelist, w)
+    }
+}
+
+/// Serializable golden test-vector document for a single frame.
+///
+/// Written by [`Frame::to_vector_file`] and replayed by [`Frame::from_vector_file`].
+/// Every HWORD is stored as its 24-character big-endian hex string so the vector
+/// is human-diffable and independent of the binary `.dsql` layout.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameVector {
+    pub frame_id: u32,
+    pub registers: Vec<u16>,
+    pub hwords: Vec<String>,
+    pub coordinates: CoordinateData,
+}
+
+#[cfg(feature = "std")]
+impl FrameVector {
+    /// Capture `frame` into its vector representation.
+    fn from_frame(frame: &Frame) -> Self {
+        let mut hwords = Vec::with_capacity(frame.header.hwords.len() + frame.pixels.hwords.len());
+        for hword in frame.header.hwords.iter().chain(frame.pixels.hwords.iter()) {
+            hwords.push(encode_hword_hex(&hword.to_bytes()));
+        }
+        Self {
+            frame_id: frame.frame_id,
+            registers: frame.header.registers.clone(),
+            hwords,
+            coordinates: frame.pixels.extract_coordinates(&FieldWhitelist::all(), 1, None),
+        }
+    }
+}
+
+/// Render 12 HWORD bytes as a 24-character lowercase hex string.
+#[cfg(feature = "std")]
+fn encode_hword_hex(bytes: &[u8; 12]) -> String {
+    use core::fmt::Write;
+    let mut s = String::with_capacity(24);
+    for byte in bytes {
+        let _ = write!(s, "{:02x}", byte);
+    }
+    s
+}
+
+/// Parse a 24-character hex HWORD string back into its 12 bytes.
+#[cfg(feature = "std")]
+fn decode_hword_hex(hex: &str) -> Result<[u8; 12]> {
+    if hex.len() != 24 {
+        return Err(SharedError::invalid_file_format(
+            "HWORD hex string must be exactly 24 characters",
+        ));
+    }
+    let mut bytes = [0u8; 12];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| SharedError::invalid_file_format("Invalid hex digit in HWORD vector"))?;
     }
+    Ok(bytes)
 }
 
 /// Extract frame number from .dsql file path
+#[cfg(feature = "std")]
 fn extract_frame_number_from_path(path: &Path) -> Result<u32> {
     let filename = path.file_stem()
         .and_then(|s| s.to_str())
@@ -286,6 +759,285 @@ fn extract_frame_number_from_path(path: &Path) -> Result<u32> {
     Ok((hash & 0xFFFFFFFF) as u32)
 }
 
+/// Streaming state machine that reconstructs [`Frame`]s from a continuous,
+/// chunk-at-a-time byte stream (a socket `recv`, a DMA buffer, etc.).
+///
+/// Unlike [`Frame::from_bytes`], which needs a whole frame up front and insists
+/// the buffer be a multiple of [`HWORD_SIZE_BYTES`](crate::protocol::HWORD_SIZE_BYTES),
+/// the assembler tolerates arbitrary slice boundaries: it carries a partial-HWORD
+/// tail (fewer than 12 bytes) and a partially-built `Frame` across [`push`](Self::push)
+/// calls, emits each frame as the next one starts, and recovers from desync by
+/// reporting a recoverable error and resynchronising on the next `FirstHeader`.
+pub struct FrameAssembler {
+    /// Bytes of an HWORD that arrived split across a `push` boundary.
+    tail: Vec<u8>,
+    /// Frame currently being accumulated, if any.
+    current: Option<Frame>,
+    /// Whether we are still collecting header HWORDs for `current`.
+    in_header: bool,
+    /// Frame id handed to the next frame that starts.
+    next_frame_id: u32,
+    /// Completed frames buffered for the non-blocking [`drain_frames`](Self::drain_frames)
+    /// path, fed by [`push_bytes`](Self::push_bytes).
+    ready: VecDeque<Frame>,
+    /// Count of recoverable stream faults (bad HWORDs, short frames) seen by
+    /// [`push_bytes`](Self::push_bytes).
+    dropped: u64,
+    /// Clock used to stamp each frame as it completes, if any.
+    clock: Option<Arc<dyn Clocks>>,
+}
+
+impl FrameAssembler {
+    /// Create a new, empty assembler starting at frame id 0.
+    pub fn new() -> Self {
+        Self {
+            tail: Vec::new(),
+            current: None,
+            in_header: false,
+            next_frame_id: 0,
+            ready: VecDeque::new(),
+            dropped: 0,
+            clock: None,
+        }
+    }
+
+    /// Create an assembler that stamps each completed frame with `clock`.
+    pub fn with_clock(clock: Arc<dyn Clocks>) -> Self {
+        Self {
+            clock: Some(clock),
+            ..Self::new()
+        }
+    }
+
+    /// Feed a slice of the byte stream and return any frames that completed
+    /// during this push.
+    ///
+    /// Completed frames are yielded as `Ok(Frame)`; recoverable stream faults
+    /// (an unparseable HWORD, or a short frame abandoned after a mid-frame
+    /// `FirstHeader`) are yielded as `Err(_)` so the caller can count drops
+    /// without tearing down the stream. Unfinished frames stay buffered for the
+    /// next push; call [`flush`](Self::flush) to force the trailing frame out.
+    pub fn push(&mut self, bytes: &[u8]) -> alloc::vec::IntoIter<Result<Frame>> {
+        let mut out: Vec<Result<Frame>> = Vec::new();
+
+        let mut buf = core::mem::take(&mut self.tail);
+        buf.extend_from_slice(bytes);
+
+        let size = crate::protocol::HWORD_SIZE_BYTES;
+        let mut offset = 0;
+        while buf.len() - offset >= size {
+            let chunk: [u8; 12] = buf[offset..offset + size]
+                .try_into()
+                .expect("slice is exactly HWORD_SIZE_BYTES");
+            offset += size;
+            self.feed_hword(&chunk, &mut out);
+        }
+
+        // Carry the unaligned remainder into the tail for the next push.
+        self.tail = buf.split_off(offset);
+
+        out.into_iter()
+    }
+
+    /// Feed a slice of the byte stream, buffering any completed frames, and
+    /// return the frames that finished during this push.
+    ///
+    /// This is the blocking "drain all complete frames" style: the returned
+    /// `Vec` is ready to process inline. The same frames are also enqueued for
+    /// the non-blocking path, so a capture thread can instead discard the return
+    /// value and let a downstream consumer poll [`frame_ready`](Self::frame_ready)
+    /// and [`drain_frames`](Self::drain_frames) — pick one consumption style per
+    /// assembler, not both. Recoverable faults are counted (see
+    /// [`dropped`](Self::dropped)) rather than returned.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Vec<Frame> {
+        let before = self.ready.len();
+        for result in self.push(bytes) {
+            match result {
+                Ok(frame) => self.ready.push_back(frame),
+                Err(_) => self.dropped += 1,
+            }
+        }
+        self.ready.iter().skip(before).cloned().collect()
+    }
+
+    /// Whether at least one completed frame is waiting to be drained.
+    ///
+    /// Non-blocking: lets the capture thread keep pushing without stalling on
+    /// downstream processing.
+    pub fn frame_ready(&self) -> bool {
+        !self.ready.is_empty()
+    }
+
+    /// Remove and return every completed frame buffered so far.
+    pub fn drain_frames(&mut self) -> Vec<Frame> {
+        self.ready.drain(..).collect()
+    }
+
+    /// Number of recoverable stream faults observed by [`push_bytes`](Self::push_bytes).
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Emit the frame currently being assembled, if it carries any HWORDs.
+    ///
+    /// Call this once the stream ends so the final frame is not lost.
+    pub fn flush(&mut self) -> Option<Result<Frame>> {
+        let clock = self.clock.clone();
+        self.take_current().map(|mut frame| {
+            frame.header.extract_registers()?;
+            if let Some(clock) = &clock {
+                frame.timestamp = Some(clock.now());
+            }
+            Ok(frame)
+        })
+    }
+
+    /// Number of bytes buffered as a partial HWORD awaiting more data.
+    pub fn pending_bytes(&self) -> usize {
+        self.tail.len()
+    }
+
+    fn feed_hword(&mut self, chunk: &[u8; 12], out: &mut Vec<Result<Frame>>) {
+        let hword = match HWord::from_bytes(chunk) {
+            Ok(h) => h,
+            Err(e) => {
+                // Skip the bad word but keep the stream alive.
+                out.push(Err(SharedError::from(e)));
+                return;
+            }
+        };
+
+        match hword.control_bits {
+            ControlBits::FirstHeader => {
+                // A start-of-frame boundary. Emit whatever we had: a complete
+                // frame as Ok, an unfinished one as a recoverable desync error.
+                if let Some(frame) = self.take_current() {
+                    out.push(self.finish(frame));
+                }
+                let mut frame = Frame::new(self.next_frame_id);
+                self.next_frame_id = self.next_frame_id.wrapping_add(1);
+                let _ = frame.header.add_hword(hword);
+                self.in_header = true;
+                self.current = Some(frame);
+            }
+            ControlBits::SubsequentHeader => {
+                if let Some(frame) = self.current.as_mut() {
+                    if self.in_header {
+                        let _ = frame.header.add_hword(hword);
+                    }
+                }
+            }
+            ControlBits::FirstPixel => {
+                if let Some(frame) = self.current.as_mut() {
+                    self.in_header = false;
+                    let _ = frame.pixels.add_hword(hword);
+                }
+            }
+            ControlBits::SubsequentPixel => {
+                if let Some(frame) = self.current.as_mut() {
+                    if !self.in_header {
+                        let _ = frame.pixels.add_hword(hword);
+                    }
+                }
+            }
+            // Idle and reserved words are stream filler; ignore them.
+            _ => {}
+        }
+    }
+
+    /// Take ownership of the in-progress frame, resetting header state.
+    fn take_current(&mut self) -> Option<Frame> {
+        self.in_header = false;
+        self.current.take()
+    }
+
+    /// Validate a frame that ended on a boundary and extract its registers.
+    fn finish(&self, mut frame: Frame) -> Result<Frame> {
+        if !frame.header.is_complete() {
+            return Err(SharedError::invalid_frame(format!(
+                "short frame {}: {} of {} header HWORDs before resync",
+                frame.frame_id,
+                frame.header.hwords.len(),
+                crate::protocol::HEADER_HWORDS_PER_FRAME
+            )));
+        }
+        frame.header.extract_registers()?;
+        if let Some(clock) = &self.clock {
+            frame.timestamp = Some(clock.now());
+        }
+        Ok(frame)
+    }
+}
+
+impl Default for FrameAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forward-migration of frames captured under older layout versions.
+///
+/// Because components deploy independently, a capture may arrive tagged with an
+/// older [`FORMAT_VERSION`](crate::protocol::FORMAT_VERSION) than this build
+/// understands. The [`Migrate`] trait decodes such a frame at its declared
+/// version and walks it forward one step at a time to the current
+/// representation. Versions newer than this build are rejected with
+/// [`SharedError::UnsupportedVersion`] rather than mis-parsed.
+pub mod migrate {
+    use super::{Frame, Result, SharedError};
+
+    /// Decodes a frame tagged with an arbitrary historical version and upgrades
+    /// it to the current in-memory [`Frame`] representation.
+    pub trait Migrate {
+        /// Decode `raw` as format `version` and migrate it forward to current.
+        fn migrate_from(version: u16, raw: &[u8]) -> Result<Frame>;
+    }
+
+    /// Default migrator covering every layout version this build ships.
+    pub struct FrameMigrator;
+
+    impl Migrate for FrameMigrator {
+        fn migrate_from(version: u16, raw: &[u8]) -> Result<Frame> {
+            if version > crate::protocol::FORMAT_VERSION {
+                return Err(SharedError::UnsupportedVersion(version));
+            }
+
+            // Decode at the frame's own version, then apply each upgrade step in
+            // turn until we reach the current representation.
+            let mut frame = decode_at(version, raw)?;
+            let mut v = version;
+            while v < crate::protocol::FORMAT_VERSION {
+                frame = upgrade_step(v, frame)?;
+                v += 1;
+            }
+            frame.header.version = crate::protocol::FORMAT_VERSION;
+            Ok(frame)
+        }
+    }
+
+    /// Per-version decode path. New historical formats add an arm here.
+    fn decode_at(version: u16, raw: &[u8]) -> Result<Frame> {
+        match version {
+            // v0 and v1 share the current 96-bit HWORD layout.
+            0 | 1 => {
+                let mut frame = Frame::from_bytes(0, raw)?;
+                frame.header.version = version;
+                Ok(frame)
+            }
+            other => Err(SharedError::UnsupportedVersion(other)),
+        }
+    }
+
+    /// Upgrade a decoded frame from `from` to `from + 1`. Arms are added as the
+    /// layout evolves; today every step is structurally a no-op.
+    fn upgrade_step(from: u16, frame: Frame) -> Result<Frame> {
+        match from {
+            0 => Ok(frame), // v0 -> v1: no layout change yet
+            other => Err(SharedError::UnsupportedVersion(other)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,4 +1066,199 @@ mod tests {
         assert!(frame.header.hwords.is_empty());
         assert!(frame.pixels.is_empty());
     }
+
+    fn hword_bytes(control: ControlBits) -> [u8; 12] {
+        HWord {
+            control_bits: control,
+            parity: false,
+            data: [0; 11],
+            remaining_bits: 0,
+        }
+        .to_bytes()
+    }
+
+    /// The header register map yields the declared pixel count and geometry, and
+    /// `num_pixels` reports the declared count rather than the actual HWORD count.
+    #[test]
+    fn test_header_layout_declared_fields() {
+        let layout = HeaderLayout::default();
+        let mut header = FrameHeader::new();
+        // Flat register stream: [count_lo, count_hi, width, height, scan_mode].
+        // 70_000 = 0x1_1170 -> low word 0x1170, high word 0x1.
+        header.registers = alloc::vec![0x1170, 0x0001, 640, 480, 2];
+
+        assert_eq!(header.expected_pixel_count(&layout), Some(70_000));
+        assert_eq!(header.frame_width(&layout), Some(640));
+        assert_eq!(header.frame_height(&layout), Some(480));
+        assert_eq!(header.scan_mode(&layout), Some(2));
+
+        let mut frame = Frame::new(1);
+        frame.header = header;
+        // Declared count comes from the header, not the (empty) pixel list.
+        assert_eq!(frame.num_pixels(), 70_000);
+    }
+
+    /// A frame frozen as a golden vector reloads into an identical frame, with
+    /// the register and coordinate checks passing.
+    #[test]
+    fn test_vector_file_roundtrip() {
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+        for _ in 1..crate::protocol::HEADER_HWORDS_PER_FRAME {
+            stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+        }
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstPixel));
+        let frame = Frame::from_bytes(9, &stream).unwrap();
+
+        let path = std::env::temp_dir().join("frame_vector_roundtrip_9.json");
+        frame.to_vector_file(&path).unwrap();
+        let reloaded = Frame::from_vector_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(reloaded.frame_id, frame.frame_id);
+        assert_eq!(reloaded.header.hwords, frame.header.hwords);
+        assert_eq!(reloaded.pixels.hwords, frame.pixels.hwords);
+    }
+
+    /// A frame round-trips through the LEB128-compressed form, preserving the
+    /// header HWORDs and the pixel payload values.
+    #[test]
+    fn test_leb128_frame_roundtrip() {
+        let mut frame = Frame::new(7);
+        frame.header.add_hword(HWord::from_bytes(&hword_bytes(ControlBits::FirstHeader)).unwrap()).unwrap();
+
+        let mut pixel = HWord {
+            control_bits: ControlBits::FirstPixel,
+            parity: true,
+            data: [0; 11],
+            remaining_bits: 0,
+        };
+        pixel.data[0] = 0x2A; // small value -> one-byte LEB128 group
+        frame.pixels.add_hword(pixel.clone()).unwrap();
+
+        let encoded = frame.to_leb128_bytes();
+        assert_eq!(encoded[0], PayloadEncoding::Leb128 as u8);
+
+        let decoded = Frame::from_leb128_bytes(7, &encoded).unwrap();
+        assert_eq!(decoded.header.hwords.len(), 1);
+        assert_eq!(decoded.pixels.len(), 1);
+        assert_eq!(decoded.pixels.hwords[0].data_as_u128(), pixel.data_as_u128());
+        assert_eq!(decoded.pixels.hwords[0].control_bits, ControlBits::FirstPixel);
+    }
+
+    /// A full frame emits once the next frame's `FirstHeader` arrives, even when
+    /// the byte stream is split at an arbitrary (non-HWORD-aligned) boundary.
+    #[test]
+    fn test_assembler_reassembles_across_split() {
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+        for _ in 1..crate::protocol::HEADER_HWORDS_PER_FRAME {
+            stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+        }
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstPixel));
+        // Start of the next frame, which flushes the first one.
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+
+        // Split mid-HWORD so the tail-carry path is exercised.
+        let split = 7;
+        let mut assembler = FrameAssembler::new();
+        let mut frames: Vec<Frame> = assembler.push(&stream[..split]).filter_map(Result::ok).collect();
+        frames.extend(assembler.push(&stream[split..]).filter_map(Result::ok));
+
+        assert_eq!(frames.len(), 1);
+        let frame = &frames[0];
+        assert_eq!(frame.frame_id, 0);
+        assert!(frame.header.is_complete());
+        assert_eq!(frame.pixels.len(), 1);
+    }
+
+    /// `push_bytes` buffers completed frames so the non-blocking `frame_ready`/
+    /// `drain_frames` pair can consume them off the capture thread.
+    #[test]
+    fn test_assembler_push_bytes_drain_and_peek() {
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+        for _ in 1..crate::protocol::HEADER_HWORDS_PER_FRAME {
+            stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+        }
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstPixel));
+        // The next frame's FirstHeader flushes the first frame.
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+
+        let mut assembler = FrameAssembler::new();
+        assert!(!assembler.frame_ready());
+        let completed = assembler.push_bytes(&stream);
+
+        assert_eq!(completed.len(), 1);
+        assert!(assembler.frame_ready());
+        let drained = assembler.drain_frames();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].frame_id, 0);
+        assert!(!assembler.frame_ready());
+        assert_eq!(assembler.dropped(), 0);
+    }
+
+    /// A clock-backed assembler stamps each completed frame, and consecutive
+    /// frames carry the simulated clock's fixed step apart.
+    #[test]
+    fn test_assembler_stamps_frames_from_clock() {
+        use crate::clock::SimulatedClock;
+
+        let mut stream = Vec::new();
+        for _ in 0..2 {
+            stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+            for _ in 1..crate::protocol::HEADER_HWORDS_PER_FRAME {
+                stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+            }
+            stream.extend_from_slice(&hword_bytes(ControlBits::FirstPixel));
+        }
+
+        let clock = Arc::new(SimulatedClock::new(0, 1_000));
+        let mut assembler = FrameAssembler::with_clock(clock);
+        let first = assembler.push_bytes(&stream);
+        assert_eq!(first.len(), 1);
+        let second = assembler.flush().unwrap().unwrap();
+
+        let t0 = first[0].timestamp.unwrap();
+        let t1 = second.timestamp.unwrap();
+        assert_eq!(t1.monotonic_since(&t0), 1_000);
+    }
+
+    #[test]
+    fn test_migrate_rejects_future_version() {
+        use migrate::{FrameMigrator, Migrate};
+        let err = FrameMigrator::migrate_from(crate::protocol::FORMAT_VERSION + 1, &[]);
+        assert!(matches!(err, Err(SharedError::UnsupportedVersion(_))));
+    }
+
+    #[test]
+    fn test_migrate_upgrades_legacy_frame() {
+        use migrate::{FrameMigrator, Migrate};
+
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+        for _ in 1..crate::protocol::HEADER_HWORDS_PER_FRAME {
+            stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+        }
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstPixel));
+
+        let frame = FrameMigrator::migrate_from(0, &stream).unwrap();
+        assert_eq!(frame.header.version, crate::protocol::FORMAT_VERSION);
+    }
+
+    /// A `FirstHeader` appearing before the header is complete is reported as a
+    /// recoverable short-frame error rather than silently accepted.
+    #[test]
+    fn test_assembler_reports_short_frame() {
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+        stream.extend_from_slice(&hword_bytes(ControlBits::SubsequentHeader));
+        // Desync: a new frame starts after only 2 header HWORDs.
+        stream.extend_from_slice(&hword_bytes(ControlBits::FirstHeader));
+
+        let mut assembler = FrameAssembler::new();
+        let results: Vec<_> = assembler.push(&stream).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
 }
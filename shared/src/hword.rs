@@ -5,59 +5,17 @@ This module provides the core HWORD data structure and parsing logic
 used throughout the frame processing pipeline.
 */
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-/// Control bit values for HWORDs according to the protocol specification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[repr(u8)]
-pub enum ControlBits {
-    Reserved0 = 0b000,
-    Reserved1 = 0b001,
-    FirstHeader = 0b010,
-    SubsequentHeader = 0b011,
-    FirstPixel = 0b100,
-    SubsequentPixel = 0b101,
-    Reserved6 = 0b110,
-    Idle = 0b111,
-}
-
-impl ControlBits {
-    /// Parse control bits from a u8 value
-    pub fn from_u8(value: u8) -> Option<Self> {
-        match value & 0b111 {
-            0b000 => Some(Self::Reserved0),
-            0b001 => Some(Self::Reserved1),
-            0b010 => Some(Self::FirstHeader),
-            0b011 => Some(Self::SubsequentHeader),
-            0b100 => Some(Self::FirstPixel),
-            0b101 => Some(Self::SubsequentPixel),
-            0b110 => Some(Self::Reserved6),
-            0b111 => Some(Self::Idle),
-            _ => None,
-        }
-    }
-    
-    /// Check if this is a header HWORD
-    pub fn is_header(self) -> bool {
-        matches!(self, Self::FirstHeader | Self::SubsequentHeader)
-    }
-    
-    /// Check if this is a pixel HWORD
-    pub fn is_pixel(self) -> bool {
-        matches!(self, Self::FirstPixel | Self::SubsequentPixel)
-    }
-    
-    /// Check if this is a frame start HWORD
-    pub fn is_frame_start(self) -> bool {
-        matches!(self, Self::FirstHeader)
-    }
-    
-    /// Check if this is an idle HWORD
-    pub fn is_idle(self) -> bool {
-        matches!(self, Self::Idle)
-    }
-}
+// The `ControlBits` enum, its `from_u8`/classifier methods, the bit shift/mask
+// constants used below, and `PROTOCOL_VERSION` are generated at build time from
+// `protocol.spec` (see `build.rs`). Retarget a firmware revision by editing the
+// spec rather than the bit math here.
+include!(concat!(env!("OUT_DIR"), "/protocol.rs"));
 
 /// Errors that can occur during HWORD parsing
 #[derive(Error, Debug)]
@@ -75,8 +33,89 @@ pub enum HWordError {
     InvalidDataField,
 }
 
+/// Byte order of the 96-bit HWORD stream.
+///
+/// The original firmware emits big-endian words (the most significant byte of
+/// the 96-bit word first), but some capture devices emit little-endian streams.
+/// Threading this through the generator and parser lets the crate interoperate
+/// with either without rewriting the bit-shifting by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+/// Internal byte-order policy for a 96-bit word, modeled on `byteorder`'s
+/// `ByteOrder` marker trait: concrete zero-sized types implement it and callers
+/// pick one as a type parameter. [`Endianness`] provides the runtime dispatch.
+pub(crate) trait Word96Order {
+    /// Reconstruct the 96-bit word from 12 raw bytes.
+    fn read_word96(bytes: &[u8; 12]) -> u128;
+    /// Serialize a 96-bit word into 12 raw bytes.
+    fn write_word96(word: u128) -> [u8; 12];
+}
+
+/// Most-significant byte first (the firmware's native order).
+pub(crate) enum BigEndian {}
+/// Least-significant byte first.
+pub(crate) enum LittleEndian {}
+
+impl Word96Order for BigEndian {
+    fn read_word96(bytes: &[u8; 12]) -> u128 {
+        let mut word = 0u128;
+        for (i, &byte) in bytes.iter().enumerate() {
+            word |= (byte as u128) << (88 - i * 8);
+        }
+        word
+    }
+
+    fn write_word96(word: u128) -> [u8; 12] {
+        let mut bytes = [0u8; 12];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = ((word >> (88 - i * 8)) & 0xFF) as u8;
+        }
+        bytes
+    }
+}
+
+impl Word96Order for LittleEndian {
+    fn read_word96(bytes: &[u8; 12]) -> u128 {
+        let mut word = 0u128;
+        for (i, &byte) in bytes.iter().enumerate() {
+            word |= (byte as u128) << (i * 8);
+        }
+        word
+    }
+
+    fn write_word96(word: u128) -> [u8; 12] {
+        let mut bytes = [0u8; 12];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = ((word >> (i * 8)) & 0xFF) as u8;
+        }
+        bytes
+    }
+}
+
+impl Endianness {
+    /// Reconstruct the 96-bit word from 12 bytes in this byte order.
+    pub fn read_word96(self, bytes: &[u8; 12]) -> u128 {
+        match self {
+            Endianness::Big => BigEndian::read_word96(bytes),
+            Endianness::Little => LittleEndian::read_word96(bytes),
+        }
+    }
+
+    /// Serialize a 96-bit word into 12 bytes in this byte order.
+    pub fn write_word96(self, word: u128) -> [u8; 12] {
+        match self {
+            Endianness::Big => BigEndian::write_word96(word),
+            Endianness::Little => LittleEndian::write_word96(word),
+        }
+    }
+}
+
 /// A 96-bit HWORD (12 bytes) as defined in the protocol
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HWord {
     pub control_bits: ControlBits,
     pub parity: bool,
@@ -85,25 +124,35 @@ pub struct HWord {
 }
 
 impl HWord {
-    /// Parse an HWORD from 12 bytes of raw data
+    /// Parse a big-endian HWORD from 12 bytes of raw data.
     pub fn from_bytes(bytes: &[u8; 12]) -> Result<Self, HWordError> {
-        // Extract control bits directly from the first byte (top 3 bits)
-        let raw_control_bits = (bytes[0] >> 5) & 0x7;
-        
+        Self::from_bytes_with_order(bytes, Endianness::Big)
+    }
+
+    /// Parse an HWORD from 12 bytes, interpreting them in `endianness`.
+    pub fn from_bytes_with_order(bytes: &[u8; 12], endianness: Endianness) -> Result<Self, HWordError> {
+        // Reconstruct the 96-bit word in the requested byte order, then work on
+        // the word value so the field extraction below is order-agnostic.
+        let word_96bit = endianness.read_word96(bytes);
+        Self::from_word96(word_96bit)
+    }
+
+    /// Split a reconstructed 96-bit word into its control/parity/data fields.
+    ///
+    /// Shared by the single-word and batch parsers so they cannot drift: the
+    /// only difference between them is how the raw bytes become `word_96bit`.
+    fn from_word96(word_96bit: u128) -> Result<Self, HWordError> {
+        // Extract control bits (top bits of the 96-bit word)
+        let raw_control_bits = ((word_96bit >> CONTROL_SHIFT) & CONTROL_MASK as u128) as u8;
+
         let control_bits = ControlBits::from_u8(raw_control_bits)
             .ok_or(HWordError::InvalidControlBits(raw_control_bits))?;
 
-        // Reconstruct the 96-bit word from big-endian bytes
-        let mut word_96bit: u128 = 0;
-        for (i, &byte) in bytes.iter().enumerate() {
-            word_96bit |= (byte as u128) << (88 - i * 8);
-        }
-
-        // Extract parity bit (bit 92)
-        let parity = ((word_96bit >> 92) & 0x1) != 0;
+        // Extract parity bit
+        let parity = ((word_96bit >> PARITY_SHIFT) & 0x1) != 0;
 
-        // Extract the 92-bit data field (bits 91-0)
-        let data_92bit = word_96bit & ((1u128 << 92) - 1);
+        // Extract the data field (low bits)
+        let data_92bit = word_96bit & DATA_FIELD_MASK;
 
         // Pack into 11 bytes + 4 remaining bits
         let mut data = [0u8; 11];
@@ -119,9 +168,87 @@ impl HWord {
             remaining_bits,
         })
     }
-    
-    /// Convert HWORD back to 12 bytes
+
+    /// Reconstruct a big-endian 96-bit word from 12 bytes via a single aligned
+    /// 128-bit load.
+    ///
+    /// The 12 payload bytes are placed in the low 96 bits of a zero-padded
+    /// 16-byte buffer and read with one [`u128::from_be_bytes`], masking off the
+    /// high 32 padding bits. This replaces the 12-iteration shift loop on the
+    /// capture hot path where an HWORD is parsed per received word.
+    #[inline]
+    fn read_word96_be(bytes: &[u8; 12]) -> u128 {
+        let mut buf = [0u8; 16];
+        buf[4..].copy_from_slice(bytes);
+        u128::from_be_bytes(buf) & ((1u128 << WORD_BITS) - 1)
+    }
+
+    /// Parse a big-endian HWORD stream as a lazy iterator of words.
+    ///
+    /// Length alignment is validated once up front: if `bytes` is not a whole
+    /// number of 12-byte words the iterator yields a single
+    /// [`HWordError::InvalidLength`] and nothing else. Otherwise each word is
+    /// decoded through the batched [`read_word96_be`](Self::read_word96_be)
+    /// load. Use this over calling [`from_bytes`](Self::from_bytes) in a loop
+    /// when draining a whole UDP packet.
+    pub fn parse_stream(bytes: &[u8]) -> impl Iterator<Item = Result<HWord, HWordError>> + '_ {
+        let misaligned = (bytes.len() % 12 != 0).then_some(bytes.len());
+        // When misaligned, walk no words; the prepended error is the only item.
+        let aligned = if misaligned.is_some() { &bytes[..0] } else { bytes };
+        misaligned
+            .map(|len| Err(HWordError::InvalidLength(len)))
+            .into_iter()
+            .chain(aligned.chunks_exact(12).map(|chunk| {
+                let word: &[u8; 12] = chunk.try_into().expect("chunks_exact(12) yields 12 bytes");
+                Self::from_word96(Self::read_word96_be(word))
+            }))
+    }
+
+    /// Parse a big-endian HWORD stream, appending the decoded words to `out`.
+    ///
+    /// Validates length alignment once, reserves capacity for the whole packet,
+    /// and stops at the first malformed word (returning its error with the words
+    /// decoded so far already pushed).
+    #[cfg(feature = "alloc")]
+    pub fn parse_into(bytes: &[u8], out: &mut Vec<HWord>) -> Result<(), HWordError> {
+        if bytes.len() % 12 != 0 {
+            return Err(HWordError::InvalidLength(bytes.len()));
+        }
+        out.reserve(bytes.len() / 12);
+        for chunk in bytes.chunks_exact(12) {
+            let word: &[u8; 12] = chunk.try_into().expect("chunks_exact(12) yields 12 bytes");
+            out.push(Self::from_word96(Self::read_word96_be(word))?);
+        }
+        Ok(())
+    }
+
+    /// Classify every word in a big-endian stream by control bits alone.
+    ///
+    /// Only the first byte of each 12-byte word is touched — the control field
+    /// lives in the top bits of the word — so frame boundaries (the next
+    /// [`ControlBits::FirstHeader`]) can be located without reconstructing the
+    /// parity and 92-bit payload of every HWORD. A trailing partial word is
+    /// ignored. Every 3-bit value is a valid enumerant, so no word is dropped.
+    #[cfg(feature = "alloc")]
+    pub fn classify_controls(bytes: &[u8]) -> Vec<ControlBits> {
+        // Bit position of the control field within byte 0 of a big-endian word.
+        let byte0_shift = CONTROL_SHIFT - (WORD_BITS - 8);
+        bytes
+            .chunks_exact(12)
+            .map(|chunk| {
+                let raw = (chunk[0] >> byte0_shift) & CONTROL_MASK;
+                ControlBits::from_u8(raw).expect("every control-bit value is enumerated")
+            })
+            .collect()
+    }
+
+    /// Convert the HWORD back to 12 big-endian bytes.
     pub fn to_bytes(&self) -> [u8; 12] {
+        self.to_bytes_with_order(Endianness::Big)
+    }
+
+    /// Convert the HWORD back to 12 bytes in the requested byte order.
+    pub fn to_bytes_with_order(&self, endianness: Endianness) -> [u8; 12] {
         // Reconstruct the 92-bit data field from the 11 bytes + 4 remaining bits
         let mut data_92bit: u128 = 0;
         for i in 0..11 {
@@ -130,17 +257,11 @@ impl HWord {
         data_92bit |= (self.remaining_bits as u128) << 88;
 
         // Reconstruct the full 96-bit word
-        let control_bits = (self.control_bits as u128) << 93;
-        let parity_bit = if self.parity { 1u128 << 92 } else { 0 };
+        let control_bits = (self.control_bits as u128) << CONTROL_SHIFT;
+        let parity_bit = if self.parity { 1u128 << PARITY_SHIFT } else { 0 };
         let word_96bit = control_bits | parity_bit | data_92bit;
 
-        // Convert back to 12 bytes (big-endian)
-        let mut bytes = [0u8; 12];
-        for i in 0..12 {
-            bytes[i] = ((word_96bit >> (88 - i * 8)) & 0xFF) as u8;
-        }
-
-        bytes
+        endianness.write_word96(word_96bit)
     }
     
     /// Verify the parity of this HWORD
@@ -196,6 +317,81 @@ mod tests {
         assert_eq!(original_bytes, converted_bytes);
     }
 
+    #[test]
+    fn test_hword_roundtrip_little_endian() {
+        let original_bytes = [
+            0x4F, 0x76, 0xB3, 0xBC, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0
+        ];
+
+        // Parsing and re-serializing in the same byte order must round-trip.
+        let hword = HWord::from_bytes_with_order(&original_bytes, Endianness::Little).unwrap();
+        let converted = hword.to_bytes_with_order(Endianness::Little);
+
+        assert_eq!(original_bytes, converted);
+    }
+
+    #[test]
+    fn test_word96_byte_order_reverses() {
+        // The little-endian byte layout is the big-endian layout reversed.
+        let word: u128 = 0x0123_4567_89AB_CDEF_1122_3344u128;
+        let be = Endianness::Big.write_word96(word);
+        let mut le = Endianness::Little.write_word96(word);
+        le.reverse();
+        assert_eq!(be, le);
+    }
+
+    #[test]
+    fn test_parse_stream_matches_single_word() {
+        // Two valid words back to back.
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&[0x4F, 0x76, 0xB3, 0xBC, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0]);
+        stream.extend_from_slice(&[0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45]);
+
+        let batched: Vec<HWord> = HWord::parse_stream(&stream).map(|r| r.unwrap()).collect();
+        assert_eq!(batched.len(), 2);
+        for (i, word) in batched.iter().enumerate() {
+            let one_at_a_time =
+                HWord::from_bytes(stream[i * 12..i * 12 + 12].try_into().unwrap()).unwrap();
+            assert_eq!(*word, one_at_a_time);
+        }
+    }
+
+    #[test]
+    fn test_parse_stream_rejects_misaligned_length() {
+        let stream = [0u8; 13];
+        let mut it = HWord::parse_stream(&stream);
+        assert!(matches!(it.next(), Some(Err(HWordError::InvalidLength(13)))));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_into_matches_parse_stream() {
+        let stream = [0x4F, 0x76, 0xB3, 0xBC, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+        let mut out = Vec::new();
+        HWord::parse_into(&stream, &mut out).unwrap();
+        let expected: Vec<HWord> = HWord::parse_stream(&stream).map(|r| r.unwrap()).collect();
+        assert_eq!(out, expected);
+        assert!(HWord::parse_into(&[0u8; 5], &mut out).is_err());
+    }
+
+    #[test]
+    fn test_classify_controls_reads_only_byte_zero() {
+        // Build a word with FirstHeader control bits and verify the fast path
+        // agrees with a full parse while ignoring a trailing partial word.
+        let word = HWord {
+            control_bits: ControlBits::FirstHeader,
+            parity: false,
+            data: [0xAA; 11],
+            remaining_bits: 0x5,
+        };
+        let bytes = word.to_bytes();
+        let mut stream = bytes.to_vec();
+        stream.extend_from_slice(&[0xFF, 0xFF]); // trailing partial word, ignored
+
+        let controls = HWord::classify_controls(&stream);
+        assert_eq!(controls, vec![ControlBits::FirstHeader]);
+    }
+
     #[test]
     fn test_control_bits_classification() {
         assert!(ControlBits::FirstHeader.is_header());
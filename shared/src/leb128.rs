@@ -0,0 +1,106 @@
+/*!
+Unsigned LEB128 variable-length integer coding.
+
+The fixed frame format always spends a full 92-bit data field per point, even for
+tiny readings. LEB128 instead emits the value seven bits at a time, so small
+values cost a single byte. Each byte carries seven payload bits; the high bit
+(`0x80`) is set on every byte except the last to signal continuation.
+
+A per-value [`MAX_LEB128_BYTES`] guard rejects a corrupt stream whose
+continuation bit never clears, so decoding can never run away past the widest a
+92-bit value can legally be.
+*/
+
+use crate::error::{Result, SharedError};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Maximum number of bytes a legal value may occupy.
+///
+/// A 92-bit value needs at most `ceil(92 / 7) = 14` groups of seven bits.
+pub const MAX_LEB128_BYTES: usize = 14;
+
+/// Append the unsigned LEB128 encoding of `value` to `out`.
+#[cfg(feature = "alloc")]
+pub fn encode_unsigned(mut value: u128, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80; // more groups follow
+            out.push(byte);
+        } else {
+            out.push(byte);
+            break;
+        }
+    }
+}
+
+/// Decode one unsigned LEB128 value from the front of `bytes`.
+///
+/// Returns the value and the number of bytes consumed. Fails with
+/// [`SharedError::InvalidFileFormat`] if the value is unterminated or runs past
+/// [`MAX_LEB128_BYTES`].
+#[cfg(feature = "alloc")]
+pub fn decode_unsigned(bytes: &[u8]) -> Result<(u128, usize)> {
+    let mut value: u128 = 0;
+    let mut shift: u32 = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        if i >= MAX_LEB128_BYTES {
+            return Err(SharedError::invalid_file_format(
+                "LEB128 value exceeds maximum byte count",
+            ));
+        }
+        value |= ((byte & 0x7F) as u128) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+        shift += 7;
+    }
+    Err(SharedError::invalid_file_format("Truncated LEB128 value"))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn roundtrip(value: u128) {
+        let mut buf = Vec::new();
+        encode_unsigned(value, &mut buf);
+        let (decoded, consumed) = decode_unsigned(&buf).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(consumed, buf.len());
+    }
+
+    #[test]
+    fn test_small_values_are_one_byte() {
+        let mut buf = Vec::new();
+        encode_unsigned(0, &mut buf);
+        assert_eq!(buf, [0]);
+        buf.clear();
+        encode_unsigned(127, &mut buf);
+        assert_eq!(buf, [127]);
+    }
+
+    #[test]
+    fn test_multibyte_roundtrip() {
+        roundtrip(128);
+        roundtrip(300);
+        roundtrip(u64::MAX as u128);
+        roundtrip((1u128 << 92) - 1);
+    }
+
+    #[test]
+    fn test_truncated_stream_rejected() {
+        // A lone continuation byte never terminates.
+        assert!(decode_unsigned(&[0x80]).is_err());
+    }
+
+    #[test]
+    fn test_runaway_rejected() {
+        // Fifteen continuation bytes exceed the 92-bit byte budget.
+        let runaway = [0x80u8; 15];
+        assert!(decode_unsigned(&runaway).is_err());
+    }
+}
@@ -16,20 +16,69 @@ in the Universal Instrument Control system.
 - [`hword`] - HWORD parsing and manipulation
 - [`coordinates`] - Coordinate conversion utilities
 - [`error`] - Common error types
+- [`metadata`] - Self-describing field layout descriptors
+- [`decoder`] - Streaming frame decoder over any `Read` source
+- [`clock`] - Injectable clock abstraction for deterministic frame timestamps
+- [`arrow_export`] - Columnar Apache Arrow export of decoded coordinates (feature `arrow`)
+
+## `no_std` support
+
+This crate is `#![no_std]` by default at the core, with a default `std`
+feature for host tooling. Disable default features and (optionally) enable
+`alloc` to use `hword`, `coordinates`, and `frame` in bare-metal firmware
+that reads HWORDs directly off a bus:
+
+```toml
+shared = { version = "*", default-features = false, features = ["alloc"] }
+```
+
+The heap-free HWORD parsing path (`HWord::from_bytes`, control-bit
+classification, fixed-point math) compiles with no allocator at all. The
+`Frame`/`FrameHeader` collections and `CoordinateData` require `alloc`, and
+file/I/O helpers require `std`.
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod hword;
 pub mod coordinates;
 pub mod error;
+pub mod metadata;
+pub mod clock;
+#[cfg(feature = "alloc")]
 pub mod frame;
+#[cfg(feature = "alloc")]
+pub mod transform;
+#[cfg(feature = "alloc")]
+pub mod leb128;
+#[cfg(feature = "integrity")]
+pub mod sha512;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(feature = "std")]
+pub mod decoder;
+#[cfg(feature = "arrow")]
+pub mod arrow_export;
 
 // Re-export commonly used types
-pub use hword::{HWord, ControlBits, HWordError};
-pub use coordinates::{CoordinateData, FieldWhitelist};
+pub use hword::{HWord, ControlBits, HWordError, Endianness, PROTOCOL_VERSION};
+#[cfg(feature = "alloc")]
+pub use coordinates::{BitSet, BoolColumn, Column, CoordinateData, FieldWhitelist, Roi};
+pub use coordinates::{sign_extend, FieldLayout, FieldLayoutError, FieldSpec};
 pub use error::{SharedError, Result};
-pub use frame::{Frame, FrameHeader, PixelData};
+pub use clock::{Clocks, Timestamp};
+#[cfg(feature = "alloc")]
+pub use frame::{Frame, FrameHeader, HeaderLayout, PixelData, PayloadEncoding};
+#[cfg(feature = "alloc")]
+pub use transform::{Transform, TransformChain, TransformSpec};
+#[cfg(feature = "arrow")]
+pub use arrow_export::coordinate_data_to_arrow;
 
 /// Version information for the shared library
+#[cfg(feature = "std")]
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Protocol constants
@@ -48,4 +97,12 @@ pub mod protocol {
     
     /// Fixed-point scaling factor for coordinates (2^10 = 1024)
     pub const COORDINATE_SCALE_FACTOR: f64 = 1024.0;
+
+    /// Current HWORD/frame layout version understood by this build.
+    ///
+    /// Frames carry this in their header so independently deployed components
+    /// can detect layout skew. Captures from older firmware are walked forward
+    /// by [`frame::migrate`](crate::frame::migrate); newer ones are rejected
+    /// with [`SharedError::UnsupportedVersion`](crate::error::SharedError::UnsupportedVersion).
+    pub const FORMAT_VERSION: u16 = 1;
 }
@@ -0,0 +1,149 @@
+/*!
+Self-describing field metadata for cross-language binding generation.
+
+Non-Rust components (C++/Python instrument GUIs) currently hardcode the 92-bit
+HWORD data layout. This module publishes the Rust definition of truth as a
+compact, self-describing descriptor table so a build step or runtime handshake
+can hand the layout to other languages, keeping every component in lockstep.
+
+Each [`FieldDescriptor`] serializes to a fixed 6-byte record:
+
+| bytes | meaning                                   |
+|-------|-------------------------------------------|
+| 0     | `field_id`                                |
+| 1     | `type_code` (see [`TypeCode`])            |
+| 2..4  | `bit_offset` (little-endian `u16`)        |
+| 4     | `bit_width`                               |
+| 5     | `scale` (fractional bits, `0` if none)    |
+*/
+
+/// Small integer type codes for a decoded field's wire representation.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeCode {
+    U8 = 0,
+    U16 = 1,
+    U32 = 2,
+    I32 = 3,
+    /// Signed fixed-point; the `scale` field gives the number of fractional bits.
+    Fixed = 4,
+}
+
+/// Compact, self-describing descriptor for one decoded field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    /// Stable numeric id for the field.
+    pub field_id: u8,
+    /// Wire type of the field.
+    pub type_code: TypeCode,
+    /// Bit offset of the field within the 92-bit HWORD data payload.
+    pub bit_offset: u16,
+    /// Field width in bits.
+    pub bit_width: u8,
+    /// Fractional bits for [`TypeCode::Fixed`] fields, else `0`.
+    pub scale: u8,
+}
+
+impl FieldDescriptor {
+    /// Number of bytes one descriptor occupies in the binary table.
+    pub const SERIALIZED_LEN: usize = 6;
+
+    /// Write this descriptor's 6-byte record into `buf`.
+    ///
+    /// Returns the number of bytes written. Panics if `buf` is too small.
+    pub fn write_to(&self, buf: &mut [u8]) -> usize {
+        let offset = self.bit_offset.to_le_bytes();
+        buf[0] = self.field_id;
+        buf[1] = self.type_code as u8;
+        buf[2] = offset[0];
+        buf[3] = offset[1];
+        buf[4] = self.bit_width;
+        buf[5] = self.scale;
+        Self::SERIALIZED_LEN
+    }
+}
+
+/// Stable field ids shared with external bindings.
+pub mod field_id {
+    pub const X: u8 = 0;
+    pub const Y: u8 = 1;
+    pub const Z: u8 = 2;
+    pub const INTENSITY: u8 = 3;
+    pub const OVER_RANGE: u8 = 4;
+    pub const GAIN: u8 = 5;
+}
+
+/// The canonical descriptor table for the current HWORD data layout.
+///
+/// Offsets/widths mirror the extraction in
+/// [`extract_coordinates_from_hword`](crate::coordinates::extract_coordinates_from_hword).
+static FIELDS: [FieldDescriptor; 6] = [
+    FieldDescriptor { field_id: field_id::X, type_code: TypeCode::Fixed, bit_offset: 0, bit_width: 19, scale: 10 },
+    FieldDescriptor { field_id: field_id::Y, type_code: TypeCode::Fixed, bit_offset: 24, bit_width: 19, scale: 10 },
+    FieldDescriptor { field_id: field_id::Z, type_code: TypeCode::Fixed, bit_offset: 48, bit_width: 22, scale: 10 },
+    FieldDescriptor { field_id: field_id::INTENSITY, type_code: TypeCode::U16, bit_offset: 72, bit_width: 12, scale: 0 },
+    FieldDescriptor { field_id: field_id::OVER_RANGE, type_code: TypeCode::U8, bit_offset: 90, bit_width: 1, scale: 0 },
+    FieldDescriptor { field_id: field_id::GAIN, type_code: TypeCode::U8, bit_offset: 91, bit_width: 1, scale: 0 },
+];
+
+/// Return the self-describing descriptor table for every decoded field.
+pub fn describe() -> &'static [FieldDescriptor] {
+    &FIELDS
+}
+
+/// Serialize the whole descriptor table into `buf`, returning bytes written.
+///
+/// The layout is a `u8` field count followed by each descriptor's 6-byte
+/// record. Panics if `buf` is smaller than [`serialized_len`].
+pub fn serialize_into(buf: &mut [u8]) -> usize {
+    let fields = describe();
+    buf[0] = fields.len() as u8;
+    let mut pos = 1;
+    for field in fields {
+        pos += field.write_to(&mut buf[pos..]);
+    }
+    pos
+}
+
+/// Total size of the serialized descriptor table in bytes.
+pub fn serialized_len() -> usize {
+    1 + describe().len() * FieldDescriptor::SERIALIZED_LEN
+}
+
+/// Serialize the descriptor table to an owned byte vector.
+#[cfg(feature = "alloc")]
+pub fn to_bytes() -> alloc::vec::Vec<u8> {
+    let mut buf = alloc::vec![0u8; serialized_len()];
+    serialize_into(&mut buf);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_covers_all_fields() {
+        let fields = describe();
+        assert_eq!(fields.len(), 6);
+        let x = &fields[0];
+        assert_eq!(x.field_id, field_id::X);
+        assert_eq!(x.type_code, TypeCode::Fixed);
+        assert_eq!(x.bit_width, 19);
+        assert_eq!(x.scale, 10);
+    }
+
+    #[test]
+    fn test_serialize_roundtrip_layout() {
+        let mut buf = [0u8; 64];
+        let len = serialize_into(&mut buf);
+        assert_eq!(len, serialized_len());
+        assert_eq!(buf[0] as usize, describe().len());
+
+        // First descriptor decodes back to the X field.
+        assert_eq!(buf[1], field_id::X);
+        assert_eq!(buf[2], TypeCode::Fixed as u8);
+        let offset = u16::from_le_bytes([buf[3], buf[4]]);
+        assert_eq!(offset, 0);
+    }
+}
@@ -0,0 +1,328 @@
+/*!
+Composable spatial/intensity transforms applied to decoded coordinates.
+
+Decoded [`CoordinateData`] comes straight out of the HWORD stream in the
+instrument's native frame. Downstream consumers almost always want it in some
+other frame — translated to a survey origin, scaled to metres, rotated to level,
+with intensity normalised. Rather than bake those adjustments into the decode
+loop, this module defines a [`Transform`] trait and an ordered [`TransformChain`]
+that is applied to the points before they are emitted or saved, so streamed and
+stored data is already in the target frame.
+
+The chain can be built programmatically or deserialized from a config file via
+[`TransformSpec`].
+*/
+
+use crate::coordinates::CoordinateData;
+use serde::{Deserialize, Serialize};
+
+use alloc::{boxed::Box, vec::Vec};
+
+/// A single stage in a [`TransformChain`].
+///
+/// Stages mutate the points in place and are cheap to compose. A stage that
+/// touches a field a point does not carry (e.g. [`Translate`] on a point whose
+/// `x` is `None`) leaves that field untouched.
+pub trait Transform: Send + Sync {
+    /// Apply this transform to every point in `coords`.
+    fn apply(&self, coords: &mut CoordinateData);
+}
+
+/// Ordered list of transforms applied in sequence.
+pub struct TransformChain {
+    stages: Vec<Box<dyn Transform>>,
+}
+
+impl TransformChain {
+    /// Create an empty chain (an identity transform).
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Append a stage, returning `self` for builder-style chaining.
+    pub fn push(mut self, stage: Box<dyn Transform>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Build a chain from a list of [`TransformSpec`]s, e.g. loaded from config.
+    pub fn from_specs(specs: &[TransformSpec]) -> Self {
+        let stages = specs.iter().map(TransformSpec::build).collect();
+        Self { stages }
+    }
+
+    /// Number of stages in the chain.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Whether the chain has no stages (acts as the identity transform).
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl Default for TransformChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transform for TransformChain {
+    fn apply(&self, coords: &mut CoordinateData) {
+        for stage in &self.stages {
+            stage.apply(coords);
+        }
+    }
+}
+
+/// Add a constant offset to each point's `x`/`y`/`z`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Translate {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Transform for Translate {
+    fn apply(&self, coords: &mut CoordinateData) {
+        if let Some(col) = coords.x.as_mut() { for v in &mut col.values { *v += self.x; } }
+        if let Some(col) = coords.y.as_mut() { for v in &mut col.values { *v += self.y; } }
+        if let Some(col) = coords.z.as_mut() { for v in &mut col.values { *v += self.z; } }
+    }
+}
+
+/// Multiply each point's `x`/`y`/`z` by a per-axis factor.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Scale {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self { x: 1.0, y: 1.0, z: 1.0 }
+    }
+}
+
+impl Transform for Scale {
+    fn apply(&self, coords: &mut CoordinateData) {
+        if let Some(col) = coords.x.as_mut() { for v in &mut col.values { *v *= self.x; } }
+        if let Some(col) = coords.y.as_mut() { for v in &mut col.values { *v *= self.y; } }
+        if let Some(col) = coords.z.as_mut() { for v in &mut col.values { *v *= self.z; } }
+    }
+}
+
+/// Rotate each point by a fixed 3×3 matrix.
+///
+/// Points missing an axis are treated as having `0.0` on that axis for the
+/// matrix multiply, but only the axes the point actually carries are written
+/// back, so a 2-D `(x, y)` point stays 2-D.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Rotate {
+    /// Row-major rotation matrix.
+    pub matrix: [[f64; 3]; 3],
+}
+
+impl Rotate {
+    /// Build a rotation from yaw (Z), pitch (Y) and roll (X) in radians, applied
+    /// in Z·Y·X order — the convention the instrument mounting brackets use.
+    pub fn from_yaw_pitch_roll(yaw: f64, pitch: f64, roll: f64) -> Self {
+        let (sy, cy) = (libm_sin(yaw), libm_cos(yaw));
+        let (sp, cp) = (libm_sin(pitch), libm_cos(pitch));
+        let (sr, cr) = (libm_sin(roll), libm_cos(roll));
+        // R = Rz(yaw) * Ry(pitch) * Rx(roll)
+        Self {
+            matrix: [
+                [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+                [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+                [-sp, cp * sr, cp * cr],
+            ],
+        }
+    }
+}
+
+impl Default for Rotate {
+    fn default() -> Self {
+        Self {
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+}
+
+impl Transform for Rotate {
+    fn apply(&self, coords: &mut CoordinateData) {
+        let m = &self.matrix;
+        for i in 0..coords.len() {
+            let x = coords.x.as_ref().map_or(0.0, |c| c.values[i]);
+            let y = coords.y.as_ref().map_or(0.0, |c| c.values[i]);
+            let z = coords.z.as_ref().map_or(0.0, |c| c.values[i]);
+            let rx = m[0][0] * x + m[0][1] * y + m[0][2] * z;
+            let ry = m[1][0] * x + m[1][1] * y + m[1][2] * z;
+            let rz = m[2][0] * x + m[2][1] * y + m[2][2] * z;
+            if let Some(c) = coords.x.as_mut() { c.values[i] = rx; }
+            if let Some(c) = coords.y.as_mut() { c.values[i] = ry; }
+            if let Some(c) = coords.z.as_mut() { c.values[i] = rz; }
+        }
+    }
+}
+
+/// Source axis (with optional sign flip) for an [`AxisSwap`] output axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    #[serde(rename = "x")]
+    X,
+    #[serde(rename = "-x")]
+    NegX,
+    #[serde(rename = "y")]
+    Y,
+    #[serde(rename = "-y")]
+    NegY,
+    #[serde(rename = "z")]
+    Z,
+    #[serde(rename = "-z")]
+    NegZ,
+}
+
+impl Axis {
+    fn sample(self, x: Option<f64>, y: Option<f64>, z: Option<f64>) -> Option<f64> {
+        match self {
+            Axis::X => x,
+            Axis::NegX => x.map(|v| -v),
+            Axis::Y => y,
+            Axis::NegY => y.map(|v| -v),
+            Axis::Z => z,
+            Axis::NegZ => z.map(|v| -v),
+        }
+    }
+}
+
+/// Remap the axes, e.g. to convert a right-handed to a left-handed frame or swap
+/// forward/up conventions. Each output axis names its source axis and sign.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AxisSwap {
+    pub x: Axis,
+    pub y: Axis,
+    pub z: Axis,
+}
+
+impl Default for AxisSwap {
+    fn default() -> Self {
+        Self { x: Axis::X, y: Axis::Y, z: Axis::Z }
+    }
+}
+
+impl Transform for AxisSwap {
+    fn apply(&self, coords: &mut CoordinateData) {
+        for i in 0..coords.len() {
+            let x = coords.x.as_ref().and_then(|c| c.get(i));
+            let y = coords.y.as_ref().and_then(|c| c.get(i));
+            let z = coords.z.as_ref().and_then(|c| c.get(i));
+            if let Some(c) = coords.x.as_mut() { c.set(i, self.x.sample(x, y, z)); }
+            if let Some(c) = coords.y.as_mut() { c.set(i, self.y.sample(x, y, z)); }
+            if let Some(c) = coords.z.as_mut() { c.set(i, self.z.sample(x, y, z)); }
+        }
+    }
+}
+
+/// Optional tone curve applied after gain/offset in [`IntensityRemap`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum IntensityCurve {
+    /// `out = in ^ exponent` (applied in the 0..=1 normalised domain).
+    Gamma { exponent: f64 },
+    /// `out = log2(1 + in)` companding, normalised to `[0, 1]` over the unit
+    /// input domain (so `in = 1` maps to `1`).
+    Log,
+}
+
+/// Affine intensity remap with optional clamp and tone curve.
+///
+/// Intensities are `u16`; the stage computes `gain * i + offset` in `f64`, then
+/// optionally applies `curve`, clamps to `[clamp_min, clamp_max]`, and rounds
+/// back to `u16`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IntensityRemap {
+    pub gain: f64,
+    pub offset: f64,
+    pub clamp_min: u16,
+    pub clamp_max: u16,
+    #[serde(default)]
+    pub curve: Option<IntensityCurve>,
+}
+
+impl Default for IntensityRemap {
+    fn default() -> Self {
+        Self { gain: 1.0, offset: 0.0, clamp_min: 0, clamp_max: u16::MAX, curve: None }
+    }
+}
+
+impl Transform for IntensityRemap {
+    fn apply(&self, coords: &mut CoordinateData) {
+        let span = self.clamp_max.max(self.clamp_min);
+        let Some(col) = coords.intensity.as_mut() else { return };
+        for idx in 0..col.values.len() {
+            if let Some(i) = col.get(idx) {
+                let mut v = self.gain * i as f64 + self.offset;
+                if let Some(curve) = self.curve {
+                    let scale = if span > 0 { span as f64 } else { 1.0 };
+                    let norm = (v / scale).clamp(0.0, 1.0);
+                    let mapped = match curve {
+                        IntensityCurve::Gamma { exponent } => libm_pow(norm, exponent),
+                        IntensityCurve::Log => libm_ln(1.0 + norm) / core::f64::consts::LN_2,
+                    };
+                    v = mapped * scale;
+                }
+                let clamped = v.clamp(self.clamp_min as f64, self.clamp_max as f64);
+                col.set(idx, Some(clamped.round() as u16));
+            }
+        }
+    }
+}
+
+/// Serializable description of a single transform stage, used to build a
+/// [`TransformChain`] from a config file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum TransformSpec {
+    Translate(Translate),
+    Scale(Scale),
+    Rotate(Rotate),
+    AxisSwap(AxisSwap),
+    IntensityRemap(IntensityRemap),
+}
+
+impl TransformSpec {
+    /// Materialize this spec into a boxed [`Transform`].
+    pub fn build(&self) -> Box<dyn Transform> {
+        match self {
+            TransformSpec::Translate(t) => Box::new(*t),
+            TransformSpec::Scale(s) => Box::new(*s),
+            TransformSpec::Rotate(r) => Box::new(*r),
+            TransformSpec::AxisSwap(a) => Box::new(*a),
+            TransformSpec::IntensityRemap(i) => Box::new(*i),
+        }
+    }
+}
+
+// The transform math needs a few transcendental functions. On `std` targets we
+// use the standard library; under `no_std` + `alloc` we fall back to `libm`,
+// which the firmware build already pulls in for its float routines.
+#[cfg(feature = "std")]
+fn libm_sin(x: f64) -> f64 { x.sin() }
+#[cfg(feature = "std")]
+fn libm_cos(x: f64) -> f64 { x.cos() }
+#[cfg(feature = "std")]
+fn libm_pow(x: f64, y: f64) -> f64 { x.powf(y) }
+#[cfg(feature = "std")]
+fn libm_ln(x: f64) -> f64 { x.ln() }
+
+#[cfg(not(feature = "std"))]
+fn libm_sin(x: f64) -> f64 { libm::sin(x) }
+#[cfg(not(feature = "std"))]
+fn libm_cos(x: f64) -> f64 { libm::cos(x) }
+#[cfg(not(feature = "std"))]
+fn libm_pow(x: f64, y: f64) -> f64 { libm::pow(x, y) }
+#[cfg(not(feature = "std"))]
+fn libm_ln(x: f64) -> f64 { libm::log(x) }